@@ -1,5 +1,10 @@
 use serde::Serialize;
 
+use std::collections::BTreeMap;
+
+use crate::mod_manager::integrations::StoreId;
+use crate::mod_manager::mods::ModLocation;
+
 #[derive(Serialize)]
 pub struct SidebarIcon {
     pub id: String,
@@ -7,18 +12,31 @@ pub struct SidebarIcon {
     pub icon: String,
 }
 
-#[derive(Serialize, Default)]
+/// Last selected items in the tree and the list, so the UI can restore them on start.
+#[derive(Serialize)]
+pub struct SelectionState {
+    pub tree_item: Option<String>,
+    pub tree_category: Option<String>,
+    pub list_item: Option<String>,
+}
+
+#[derive(Serialize, Clone, Default)]
 pub struct TreeCategory {
     pub id: String,
     pub name: String,
     pub children: Vec<TreeItem>,
 }
 
-#[derive(Serialize, Default)]
+#[derive(Serialize, Clone, Default, PartialEq)]
 pub struct TreeItem {
     pub id: String,
     pub name: String,
+    /// Description of the flags set on the mod, one per line, for tooltips. Empty if there are none.
     pub flags: String,
+    pub is_outdated: bool,
+    pub data_older_than_secondary: bool,
+    pub data_older_than_content: bool,
+    pub secondary_older_than_content: bool,
     pub location: String,
     pub creator: String,
     pub r#type: String,
@@ -36,6 +54,34 @@ pub struct ListItem {
     pub r#type: String,
     pub order: i32,
     pub location: String,
+    pub enabled: bool,
+}
+
+/// Payload of the metadata updated event. Only contains the mods that changed, so the UI can patch them in place.
+#[derive(Serialize, Clone, Default)]
+pub struct ModTreeUpdate {
+    pub changed: BTreeMap<String, TreeItem>,
+    pub removed: Vec<String>,
+}
+
+/// What a game supports, so the UI doesn't need to replicate the version checks.
+#[derive(Serialize)]
+pub struct GameCapabilities {
+    pub raw_db_version: i16,
+    pub supports_secondary_folder: bool,
+    pub supports_content_loading: bool,
+    pub supports_custom_mod_list: bool,
+    pub supports_exclude_pack_file: bool,
+    pub uses_movie_masking: bool,
+    pub supports_extra_launch_args: bool,
+}
+
+/// Where the secondary folder is compared to /data. If both are on the same volume, the secondary folder gives no benefit.
+#[derive(Serialize)]
+pub struct SecondaryFolderPlacement {
+    pub secondary_path: String,
+    pub data_path: String,
+    pub same_volume: bool,
 }
 
 /// Progress payload for the progress event. Basically, it's for providing a way to update the progress bar from the Rust side.
@@ -47,3 +93,328 @@ pub struct ProgressPayload {
     pub progress: i32,
     pub total: i32,
 }
+
+/// Build and data versions, for the About dialog and for bug reports.
+#[derive(Serialize, Default)]
+pub struct AboutInfo {
+    pub version: String,
+    pub version_subtitle: String,
+    pub schema_version: Option<u16>,
+    pub sql_scripts_commit: Option<String>,
+    pub supported_games: usize,
+}
+
+/// Result of checking the schema against the installed game. Dates are in seconds since the unix epoch, or 0 if unknown.
+#[derive(Serialize, Default)]
+pub struct SchemaCompatibility {
+    pub schema_loaded: bool,
+    pub schema_date: u64,
+    pub game_date: u64,
+    pub warning: Option<String>,
+}
+
+#[derive(Serialize, Clone, Copy, Debug, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum MoviePackHandlingMode {
+    Loaded,
+    Masked,
+    Excluded,
+    NotLoaded,
+}
+
+/// What will happen to a movie pack on launch, and why.
+#[derive(Serialize)]
+pub struct MoviePackHandling {
+    pub id: String,
+    pub pack: String,
+    pub handling: MoviePackHandlingMode,
+    pub reason: String,
+}
+
+#[derive(Serialize)]
+pub struct ModPathDetail {
+    pub path: String,
+    pub location: ModLocation,
+    pub active: bool,
+    pub modified: u64,
+}
+
+/// Every path a mod has been found in, in priority order. The first one is the one that gets loaded.
+#[derive(Serialize)]
+pub struct ModLocationDetail {
+    pub id: String,
+    pub store_id: StoreId,
+    pub active_path: Option<String>,
+    pub paths: Vec<ModPathDetail>,
+}
+
+/// Conversion status of a Shogun 2 map mod. Maps only show up in game if their pack has the battles and loc tables.
+#[derive(Serialize)]
+pub struct MapModStatus {
+    pub id: String,
+    pub name: String,
+    pub map_name: String,
+    pub pack_name: String,
+    pub converted: bool,
+    pub pack_path: Option<String>,
+    pub location: Option<ModLocation>,
+    pub has_battles_table: bool,
+    pub has_loc_table: bool,
+}
+
+/// Change in the position of a pack after a toggle. None means the pack is not in the list.
+#[derive(Serialize)]
+pub struct PackOrderChange {
+    pub id: String,
+    pub old_order: Option<i32>,
+    pub new_order: Option<i32>,
+}
+
+/// A mod whose pack type changed since the last scan, which changes how it's loaded.
+#[derive(Serialize)]
+pub struct PackTypeChange {
+    pub id: String,
+    pub old_type: String,
+    pub new_type: String,
+}
+
+/// Change in how a movie pack is handled on launch after a toggle.
+#[derive(Serialize)]
+pub struct MovieHandlingChange {
+    pub id: String,
+    pub old_handling: MoviePackHandlingMode,
+    pub new_handling: MoviePackHandlingMode,
+}
+
+/// Result of toggling a mod, with every other pack affected by it.
+#[derive(Serialize, Default)]
+pub struct ModToggleDiff {
+    pub items: Vec<ListItem>,
+    pub order_changes: Vec<PackOrderChange>,
+    pub movie_changes: Vec<MovieHandlingChange>,
+}
+
+/// Where a mod sits in the load order compared to vanilla and the other mods.
+#[derive(Serialize)]
+pub struct OverrideRank {
+    pub id: String,
+    pub is_movie: bool,
+    pub position: Option<usize>,
+    pub vanilla_packs: usize,
+    pub overrides_vanilla: bool,
+    pub overridden_by: Vec<String>,
+    pub summary: String,
+}
+
+/// A pack required by a loaded mod. Provided by is the installed mod or vanilla pack satisfying it, or None if it's missing.
+#[derive(Serialize)]
+pub struct ModRequirement {
+    pub id: String,
+    pub requirement: String,
+    pub provided_by: Option<String>,
+}
+
+/// A mod found by its pack name, with the category it's in.
+#[derive(Serialize)]
+pub struct FoundMod {
+    pub id: String,
+    pub category: String,
+}
+
+/// A mod installed in more than one location, with the flags used to detect stale overrides.
+#[derive(Serialize)]
+pub struct DuplicatedMod {
+    pub id: String,
+    pub name: String,
+    pub paths: Vec<ModPathDetail>,
+    pub data_older_than_secondary: bool,
+    pub data_older_than_content: bool,
+    pub secondary_older_than_content: bool,
+}
+
+/// Mods whose packs have the exact same contents, like re-uploads of the same mod.
+#[derive(Serialize)]
+pub struct DuplicatedContent {
+    pub hash: String,
+    pub mods: Vec<String>,
+}
+
+/// Result of a bulk rename. Renamed mods are (old id, new id), failed ones are (id, error).
+#[derive(Serialize, Default)]
+pub struct BulkRenameResult {
+    pub renamed: Vec<(String, String)>,
+    pub failed: Vec<(String, String)>,
+}
+
+/// Version of the [StateExport] format. Bump it on any breaking change so external tools can detect it.
+pub const STATE_EXPORT_VERSION: u32 = 1;
+
+/// Full state dump, for external tools.
+#[derive(Serialize)]
+pub struct StateExport {
+    pub version: u32,
+    pub games: Vec<ExportedGame>,
+    pub selected_game: String,
+    pub categories: BTreeMap<String, Vec<String>>,
+    pub categories_order: Vec<String>,
+    pub mods: Vec<ExportedMod>,
+    pub load_order: ExportedLoadOrder,
+}
+
+#[derive(Serialize)]
+pub struct ExportedGame {
+    pub key: String,
+    pub name: String,
+    pub path: String,
+}
+
+/// Summary of a configured game, for showing all of them at once.
+#[derive(Serialize)]
+pub struct GameOverview {
+    pub key: String,
+    pub name: String,
+    pub path: String,
+    pub selected: bool,
+    pub installed_mods: usize,
+    pub enabled_mods: usize,
+    pub can_be_locked: bool,
+    pub locked: bool,
+}
+
+/// Result of locking or unlocking a game. Error contains the reason if it failed.
+#[derive(Serialize)]
+pub struct GameLockResult {
+    pub key: String,
+    pub name: String,
+    pub locked: bool,
+    pub error: Option<String>,
+}
+
+#[derive(Serialize)]
+pub struct ExportedMod {
+    pub id: String,
+    pub name: String,
+    pub store_id: StoreId,
+    pub enabled: bool,
+    pub r#type: String,
+    pub paths: Vec<String>,
+}
+
+#[derive(Serialize)]
+pub struct ExportedLoadOrder {
+    pub automatic: bool,
+    pub mods: Vec<String>,
+    pub movies: Vec<String>,
+}
+
+/// Differences between the workshop subscriptions and the mods found in the content folder.
+/// Mods present but not subscribed are (workshop id, mod id).
+#[derive(Serialize, Default)]
+pub struct SubscriptionsReport {
+    pub subscribed_but_missing: Vec<String>,
+    pub present_but_not_subscribed: Vec<(String, String)>,
+}
+
+/// Workshop mods found in the content folder, and how many of them got their metadata from the workshop.
+#[derive(Serialize, Default)]
+pub struct WorkshopSummary {
+    pub installed: usize,
+    pub resolved: usize,
+    pub unresolved: usize,
+}
+
+/// A saved snapshot of the game config and load order. Timestamp is in seconds since the unix epoch.
+#[derive(Serialize)]
+pub struct SnapshotInfo {
+    pub label: String,
+    pub timestamp: u64,
+}
+
+/// Result of checking an imported load order. Mods are in the imported order.
+/// Mismatched mods are installed, but their pack is not the same as the one in the imported load order.
+#[derive(Serialize, Default)]
+pub struct ImportedLoadOrderReport {
+    pub mods: Vec<String>,
+    pub missing: Vec<String>,
+    pub mismatched: Vec<String>,
+}
+
+/// A file in the twpatcher folder shipped by more than one enabled mod. Only the last mod's file is used.
+#[derive(Serialize)]
+pub struct ScriptConflict {
+    pub path: String,
+    pub mods: Vec<String>,
+    pub winner: String,
+}
+
+/// A file or DB table shipped by more than one loaded pack, with the packs in load order. The first one wins.
+#[derive(Serialize)]
+pub struct PackConflict {
+    pub path: String,
+    pub mods: Vec<String>,
+    pub winner: String,
+}
+
+/// Conflicts between the loaded packs, with the load order used to decide the winners.
+#[derive(Serialize)]
+pub struct ConflictReport {
+    pub game: String,
+    pub mods: Vec<String>,
+    pub movies: Vec<String>,
+    pub file_conflicts: Vec<PackConflict>,
+    pub table_conflicts: Vec<PackConflict>,
+}
+
+/// A load order entry whose case doesn't match the file on disk.
+#[derive(Serialize)]
+pub struct CaseMismatch {
+    pub id: String,
+    pub file_name: String,
+    pub path: String,
+}
+
+/// Result of saving all the in-memory state to disk. Errors contain the reason of any failed save.
+#[derive(Serialize, Default)]
+pub struct FlushStateResult {
+    pub game_config: bool,
+    pub load_order: bool,
+    pub profiles: bool,
+    pub settings: bool,
+    pub errors: Vec<String>,
+}
+
+/// Differences between the in-memory game config and load order, and the ones saved on disk.
+#[derive(Serialize, Default)]
+pub struct UnsavedChanges {
+    pub has_changes: bool,
+    pub mods_enabled: Vec<String>,
+    pub mods_disabled: Vec<String>,
+    pub mods_moved: Vec<String>,
+    pub load_order_mode_changed: bool,
+    pub categories_added: Vec<String>,
+    pub categories_removed: Vec<String>,
+    pub categories_changed: Vec<String>,
+    pub categories_reordered: bool,
+}
+
+/// Time spent, in milliseconds, on each phase of the last game load.
+#[derive(Serialize, Clone, Default)]
+pub struct LoadTimings {
+    pub game: String,
+    pub config_load: u64,
+    pub content_scan: u64,
+    pub online_request: u64,
+    pub secondary_scan: u64,
+    pub data_scan: u64,
+    pub online_fetch: u64,
+    pub mod_tree_loading: u64,
+    pub pack_loading: u64,
+    pub total: u64,
+}
+
+/// Files written while preparing a launch, and anything that may cause problems in-game.
+#[derive(Serialize, Default)]
+pub struct LaunchPreparation {
+    pub written_paths: Vec<String>,
+    pub warnings: Vec<String>,
+}
@@ -13,11 +13,13 @@ use serde::{Deserialize, Serialize};
 use tauri::AppHandle;
 
 use std::collections::HashMap;
+use std::fs::DirBuilder;
 #[cfg(target_os = "windows")]
 use std::os::windows::process::CommandExt;
 use std::path::{Path, PathBuf};
 use std::process::Command;
 use std::sync::LazyLock;
+use std::time::{SystemTime, UNIX_EPOCH};
 
 use common_utils::sql::{ParamType, Preset, SQLScript};
 
@@ -29,8 +31,9 @@ use rpfm_lib::utils::files_from_subdir;
 use crate::mod_manager::integrations::DETACHED_PROCESS;
 use crate::mod_manager::load_order::*;
 use crate::settings::{
-    sql_presets_extracted_twpatcher_path, sql_scripts_extracted_twpatcher_path,
-    sql_scripts_local_path, sql_scripts_remote_path, temp_packs_folder,
+    AppSettings, sql_presets_extracted_twpatcher_path, sql_scripts_extracted_path,
+    sql_scripts_extracted_twpatcher_path, sql_scripts_local_path, sql_scripts_remote_path,
+    temp_packs_debug_folder, temp_packs_folder,
 };
 use crate::{GAME_CONFIG, GAME_LOAD_ORDER, SETTINGS};
 
@@ -104,6 +107,17 @@ const SUPPORTED_OPTIONS: &[(&str, &[&str])] = &[
 pub struct LaunchOptions {
     options: Vec<LaunchOption>,
     scripts: HashMap<String, SQLScript>,
+
+    // Files the scripts were loaded from. Only valid for the last generated options, so not serialized.
+    #[serde(skip)]
+    script_paths: HashMap<String, PathBuf>,
+
+    // Scripts that failed to load the last time the options were generated.
+    script_parse_errors: Vec<ScriptParseError>,
+
+    // Rebalancer candidates, with the game and load order they were calculated for.
+    #[serde(skip)]
+    rebalancer_candidates_cache: Option<(String, Vec<String>, Vec<String>)>,
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -115,6 +129,18 @@ pub struct LaunchOption {
     parameters: Vec<LaunchOptionParameter>,
     is_script: bool,
     presets: Vec<String>,
+
+    // Only for scripts: where the script used comes from, and other sources with a script with the same key.
+    #[serde(default)]
+    source: Option<ScriptSource>,
+    #[serde(default)]
+    shadowed_sources: Vec<ScriptSource>,
+
+    // Only for scripts shipped by mods: the mod it was extracted from, and if the user opted out of running it.
+    #[serde(default)]
+    origin_mod: Option<String>,
+    #[serde(default)]
+    user_disabled: bool,
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -125,6 +151,35 @@ pub struct LaunchOptionParameter {
     default: LaunchOptionValue,
 }
 
+/// Places SQL scripts can be loaded from, in order of priority.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ScriptSource {
+    Local,
+    Extracted,
+    Remote,
+}
+
+/// SQL script file that couldn't be loaded, and why.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ScriptParseError {
+    path: PathBuf,
+    source: ScriptSource,
+    error: String,
+}
+
+/// SQL script that will be passed to twpatcher on launch, with its parameters already resolved.
+#[derive(Clone, Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ActiveSqlScript {
+    key: String,
+    name: String,
+    path: PathBuf,
+    preset: Option<String>,
+    parameters: Vec<(String, String)>,
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize)]
 #[serde(tag = "type", content = "value")]
 pub enum LaunchOptionValue {
@@ -143,9 +198,13 @@ impl LaunchOptions {
         LaunchOptions {
             options: vec![],
             scripts: HashMap::new(),
+            script_paths: HashMap::new(),
+            script_parse_errors: vec![],
+            rebalancer_candidates_cache: None,
         }
     }
 
+    /// This function prepares the launch options for launching the game. Returns the path of the temp pack, if one was generated.
     pub fn prepare_launch_options(
         &mut self,
         app: &AppHandle,
@@ -153,23 +212,26 @@ impl LaunchOptions {
         game: &GameInfo,
         data_path: &Path,
         folder_list: &mut String,
-    ) -> Result<()> {
+    ) -> Result<Option<PathBuf>> {
         self.options = options.to_vec();
 
         if self.options.iter().any(|option| option.enabled) {
-            // We need to use an alternative name for Shogun 2, Rome 2, Attila and Thrones because their load order logic for movie packs seems... either different or broken.
-            let reserved_pack_name = if game.key() == KEY_SHOGUN_2
-                || game.key() == KEY_ROME_2
-                || game.key() == KEY_ATTILA
-                || game.key() == KEY_THRONES_OF_BRITANNIA
-            {
-                RESERVED_PACK_NAME_ALTERNATIVE
-            } else {
-                RESERVED_PACK_NAME
-            };
-
-            // If the reserved pack is loaded from a custom folder we need to CLEAR SAID FOLDER before anything else. Otherwise we may end up with old packs messing up stuff.
-            if *game.raw_db_version() >= 1 {
+            let (temp_path, working_directory) = temp_pack_path(app, game, data_path)?;
+
+            if SETTINGS.read().unwrap().keep_temp_packs {
+                // If we want to keep the previous temp pack, back it up before it gets overwritten.
+                if temp_path.is_file() {
+                    let timestamp = SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs();
+                    let debug_folder =
+                        temp_packs_debug_folder(app, game)?.join(timestamp.to_string());
+                    DirBuilder::new().recursive(true).create(&debug_folder)?;
+
+                    if let Some(file_name) = temp_path.file_name() {
+                        std::fs::copy(&temp_path, debug_folder.join(file_name))?;
+                    }
+                }
+            } else if *game.raw_db_version() >= 1 {
+                // If the reserved pack is loaded from a custom folder we need to CLEAR SAID FOLDER before anything else. Otherwise we may end up with old packs messing up stuff.
                 let temp_packs_folder = temp_packs_folder(app, game)?;
                 let files = files_from_subdir(&temp_packs_folder, false)?;
                 for file in &files {
@@ -177,259 +239,327 @@ impl LaunchOptions {
                 }
             }
 
-            // Support for add_working_directory seems to be only present in rome 2 and newer games. For older games, we drop the pack into /data.
-            let temp_path = if *game.raw_db_version() >= 1 {
-                let temp_packs_folder = temp_packs_folder(app, game)?;
-                let temp_path = temp_packs_folder.join(reserved_pack_name);
+            if let Some(working_directory) = working_directory {
                 folder_list.push_str(&format!(
                     "add_working_directory \"{}\";\n",
-                    temp_packs_folder.to_string_lossy()
+                    load_order_path_string(&working_directory)
                 ));
-                temp_path
-            } else {
-                data_path.join(reserved_pack_name)
-            };
-
-            // Prepare the command to generate the temp pack.
-            let mut cmd = if cfg!(target_os = "windows") {
-                let mut cmd = Command::new("cmd");
-                cmd.arg("/C");
-                cmd
-            } else {
-                Command::new("sh")
-            };
-
-            cmd.arg(&*PATCHER_PATH);
-            cmd.arg("-g");
-            cmd.arg(game.key());
-            cmd.arg("-l");
-            cmd.arg(load_order_file_name(game));
-            cmd.arg("-p");
-            cmd.arg(temp_path.to_string_lossy().to_string()); // Use a custom path out of /data, if available.
-            cmd.arg("-s"); // Skip updates. Updates will be shipped with Runcher updates.
-
-            // Logging check.
-            if self
-                .options
-                .iter()
-                .find(|option| option.key == "enable_logging")
-                .unwrap()
-                .enabled
-            {
-                cmd.arg("-e");
             }
 
-            // Skip Intros check.
-            if self
-                .options
-                .iter()
-                .find(|option| option.key == "skip_intros")
-                .unwrap()
-                .enabled
-            {
-                cmd.arg("-i");
-            }
+            let mut cmd = self.patcher_command(app, game, data_path, &temp_path)?;
 
-            // Remove Trait Limit check.
-            if self
-                .options
-                .iter()
-                .find(|option| option.key == "remove_trait_limit")
-                .unwrap()
-                .enabled
-            {
-                cmd.arg("-r");
-            }
+            #[cfg(target_os = "windows")]
+            cmd.creation_flags(DETACHED_PROCESS);
 
-            // Remove Siege Attacker check.
-            if self
-                .options
-                .iter()
-                .find(|option| option.key == "remove_siege_attacker")
-                .unwrap()
-                .enabled
-            {
-                cmd.arg("-a");
+            let mut h = cmd
+                .spawn()
+                .map_err(|err| anyhow!("Error when preparing the game patch: {}", err))?;
+            if let Ok(status) = h.wait() {
+                if !status.success() {
+                    return Err(anyhow!(
+                        "Something failed while creating the load order patch. Check the patcher terminal to see what happened."
+                    ));
+                }
             }
 
-            // Enable Dev-only UI check.
+            return Ok(Some(temp_path));
+        }
+
+        Ok(None)
+    }
+
+    /// This function returns the command line twpatcher would be called with on launch, without calling it.
+    ///
+    /// Returns None if no option is enabled, as in that case twpatcher is not called.
+    pub fn preview_patcher_command(
+        &mut self,
+        app: &AppHandle,
+        game: &GameInfo,
+        data_path: &Path,
+    ) -> Result<Option<String>> {
+        if !self.options.iter().any(|option| option.enabled) {
+            return Ok(None);
+        }
+
+        let (temp_path, _) = temp_pack_path(app, game, data_path)?;
+        let cmd = self.patcher_command(app, game, data_path, &temp_path)?;
+
+        let command_line = std::iter::once(cmd.get_program())
+            .chain(cmd.get_args())
+            .map(|arg| {
+                let arg = arg.to_string_lossy();
+                if arg.is_empty() || arg.contains(char::is_whitespace) {
+                    format!("\"{}\"", arg)
+                } else {
+                    arg.to_string()
+                }
+            })
+            .collect::<Vec<_>>()
+            .join(" ");
+
+        Ok(Some(command_line))
+    }
+
+    /// This function builds the twpatcher command for the current options.
+    fn patcher_command(
+        &mut self,
+        app: &AppHandle,
+        game: &GameInfo,
+        data_path: &Path,
+        temp_path: &Path,
+    ) -> Result<Command> {
+        let mut cmd = if cfg!(target_os = "windows") {
+            let mut cmd = Command::new("cmd");
+            cmd.arg("/C");
+            cmd
+        } else {
+            Command::new("sh")
+        };
+
+        cmd.arg(&*PATCHER_PATH);
+        cmd.arg("-g");
+        cmd.arg(game.key());
+        cmd.arg("-l");
+        cmd.arg(load_order_file_name(game));
+        cmd.arg("-p");
+        cmd.arg(temp_path.to_string_lossy().to_string()); // Use a custom path out of /data, if available.
+        cmd.arg("-s"); // Skip updates. Updates will be shipped with Runcher updates.
+
+        // Simple flags. Not all games support all of them, so don't assume they're there.
+        for (key, flag) in [
+            ("enable_logging", "-e"),
+            ("skip_intros", "-i"),
+            ("remove_trait_limit", "-r"),
+            ("remove_siege_attacker", "-a"),
+            ("enable_dev_only_ui", "-d"),
+        ] {
             if self
                 .options
                 .iter()
-                .find(|option| option.key == "enable_dev_only_ui")
-                .unwrap()
-                .enabled
+                .any(|option| option.key == key && option.enabled)
             {
-                cmd.arg("-d");
+                cmd.arg(flag);
             }
+        }
 
-            // Translations check.
-            if let Some(option) = self
-                .options
-                .iter()
-                .find(|option| option.key == "enable_translations")
-            {
-                if option.enabled {
-                    if let Some(param) = option
-                        .parameters
-                        .iter()
-                        .find(|param| param.key == "language")
-                    {
-                        if let LaunchOptionValue::Select(ref language, _) = param.value {
-                            cmd.arg("-t");
-                            cmd.arg(language);
-                        }
+        // Translations check.
+        if let Some(option) = self
+            .options
+            .iter()
+            .find(|option| option.key == "enable_translations")
+        {
+            if option.enabled {
+                if let Some(param) = option
+                    .parameters
+                    .iter()
+                    .find(|param| param.key == "language")
+                {
+                    if let LaunchOptionValue::Select(ref language, _) = param.value {
+                        cmd.arg("-t");
+                        cmd.arg(language);
                     }
                 }
             }
+        }
 
-            // Universal Rebalancer check.
-            if let Some(option) = self
-                .options
-                .iter()
-                .find(|option| option.key == "universal_rebalancer")
-            {
-                if option.enabled {
-                    if let Some(param) = option
-                        .parameters
-                        .iter()
-                        .find(|param| param.key == "base_mod")
-                    {
-                        if let LaunchOptionValue::Select(ref base_mod, _) = param.value {
-                            cmd.arg("-u");
-                            cmd.arg(base_mod);
-                        }
+        // Universal Rebalancer check.
+        if let Some(base_mod) = self.rebalancer_base_mod() {
+            // Make sure the base mod is still usable, or twpatcher will fail with a not-so-helpful error.
+            let candidates = self.rebalancer_candidates(app, game, data_path);
+            if !candidates.contains(&base_mod) {
+                return Err(anyhow!(
+                    "The Universal Rebalancer's base mod \"{}\" is no longer an enabled pack with a land_units table. Select another base mod in the launch options, or disable the Universal Rebalancer.",
+                    base_mod
+                ));
+            }
+
+            cmd.arg("-u");
+            cmd.arg(base_mod);
+        }
+
+        // Unit Multiplier check.
+        if let Some(option) = self
+            .options
+            .iter()
+            .find(|option| option.key == "unit_multiplier")
+        {
+            if option.enabled {
+                if let Some(param) = option
+                    .parameters
+                    .iter()
+                    .find(|param| param.key == "multiplier")
+                {
+                    if let LaunchOptionValue::Number(multiplier) = param.value {
+                        cmd.arg("-m");
+                        cmd.arg(multiplier.to_string());
                     }
                 }
             }
+        }
 
-            // Unit Multiplier check.
-            if let Some(option) = self
-                .options
-                .iter()
-                .find(|option| option.key == "unit_multiplier")
-            {
-                if option.enabled {
-                    if let Some(param) = option
+        // Script checks.
+        let settings = SETTINGS.read().unwrap().clone();
+        for script in self.active_sql_scripts(app, game, &settings)? {
+            cmd.arg("--sql-script");
+
+            if script.parameters.is_empty() {
+                cmd.arg(script.path);
+            } else {
+                cmd.arg(format!(
+                    "{};{}",
+                    script.path.to_string_lossy().to_string().replace("\\", "/"),
+                    script
                         .parameters
                         .iter()
-                        .find(|param| param.key == "multiplier")
-                    {
-                        if let LaunchOptionValue::Number(multiplier) = param.value {
-                            cmd.arg("-m");
-                            cmd.arg(multiplier.to_string());
-                        }
-                    }
-                }
+                        .map(|(_, value)| value.to_owned())
+                        .collect::<Vec<_>>()
+                        .join(";")
+                ));
             }
+        }
 
-            // Script checks.
-            let settings = SETTINGS.read().unwrap().clone();
-            let sql_folder_extracted = sql_scripts_extracted_twpatcher_path(app)?;
-            let sql_folder_presets = sql_presets_extracted_twpatcher_path(app)?;
-            let sql_folder_local = sql_scripts_local_path(app)?.join(game.key());
-            let sql_folder_remote = sql_scripts_remote_path(app)?.join(game.key());
+        // Extra user-provided args go last, so they can override the generated ones if twpatcher allows it.
+        cmd.args(split_args(&settings.twpatcher_extra_args));
+
+        Ok(cmd)
+    }
+
+    /// This function returns the base mod selected for the universal rebalancer, if the rebalancer is enabled.
+    fn rebalancer_base_mod(&self) -> Option<String> {
+        let option = self
+            .options
+            .iter()
+            .find(|option| option.key == "universal_rebalancer" && option.enabled)?;
+        let param = option
+            .parameters
+            .iter()
+            .find(|param| param.key == "base_mod")?;
+
+        match param.value {
+            LaunchOptionValue::Select(ref base_mod, _) => Some(base_mod.to_owned()),
+            _ => None,
+        }
+    }
 
-            self.options
+    /// This function drops the cached rebalancer candidates, so they're recalculated next time they're needed.
+    ///
+    /// Needed when a mod changes without changing the load order, like when it gets updated.
+    pub fn clear_rebalancer_candidates_cache(&mut self) {
+        self.rebalancer_candidates_cache = None;
+    }
+
+    /// This function returns the mod a SQL script was extracted from, if it was shipped by a mod.
+    pub fn script_origin_mod(&self, key: &str) -> Option<&str> {
+        self.options
+            .iter()
+            .find(|option| option.is_script && option.key == key)
+            .and_then(|option| option.origin_mod.as_deref())
+    }
+
+    /// This function returns if the user opted out of running a script shipped by a mod.
+    fn is_mod_script_disabled(
+        &self,
+        app: &AppHandle,
+        game: &GameInfo,
+        settings: &AppSettings,
+        key: &str,
+    ) -> bool {
+        let shipped_by_mod = match (self.script_paths.get(key), sql_scripts_extracted_path(app)) {
+            (Some(path), Ok(extracted_path)) => path.starts_with(extracted_path),
+            _ => false,
+        };
+
+        shipped_by_mod
+            && settings
+                .disabled_mod_scripts
+                .get(game.key())
+                .is_some_and(|keys| keys.iter().any(|disabled| disabled == key))
+    }
+
+    /// This function returns the SQL scripts that failed to load the last time the options were generated.
+    pub fn script_parse_errors(&self) -> &[ScriptParseError] {
+        &self.script_parse_errors
+    }
+
+    /// This function returns the enabled SQL scripts of the current options, as they'll be passed to twpatcher.
+    pub fn active_sql_scripts(
+        &self,
+        app: &AppHandle,
+        game: &GameInfo,
+        settings: &AppSettings,
+    ) -> Result<Vec<ActiveSqlScript>> {
+        let sql_folder_presets = sql_presets_extracted_twpatcher_path(app)?;
+
+        let presets = if sql_folder_presets.is_dir() {
+            files_from_subdir(&sql_folder_presets, false)
+                .unwrap_or_default()
                 .iter()
-                .filter(|option| option.is_script && option.enabled)
-                .filter_map(|option| {
-                    if let Some(script) = self.scripts.get(&option.key) {
-                        Some((script, option))
-                    } else {
-                        None
-                    }
-                })
-                .for_each(|(script, option)| {
-                    cmd.arg("--sql-script");
+                .filter_map(|x| Preset::read(x).ok())
+                .collect::<Vec<_>>()
+        } else {
+            vec![]
+        };
+
+        Ok(self
+            .options
+            .iter()
+            .filter(|option| option.is_script && option.enabled)
+            .filter(|option| !self.is_mod_script_disabled(app, game, settings, &option.key))
+            // Use the same file the options were generated from, so what runs is what the UI shows.
+            // Scripts without one would be passed to the patcher with an empty path, so skip them.
+            .filter_map(|option| {
+                Some((
+                    self.scripts.get(&option.key)?,
+                    self.script_paths.get(&option.key)?,
+                    option,
+                ))
+            })
+            .map(|(script, path, option)| {
+                let mut preset_used = None;
+                let mut parameters = vec![];
+
+                if !script.metadata().parameters().is_empty() {
+                    // First check if we have a preset set. If not, we can check each param.
+                    let preset_key = format!("{}:{}:preset", game.key(), option.key);
+                    let preset = settings
+                        .launch_options
+                        .get(&preset_key)
+                        .filter(|preset_value| *preset_value != "none")
+                        .and_then(|preset_value| presets.iter().find(|x| x.key() == preset_value));
+
+                    match preset {
+                        Some(preset) => {
+                            for param in script.metadata().parameters() {
+                                let value = match preset.params().get(param.key()) {
+                                    Some(value) => value.to_string(),
+                                    None => param.default_value().to_string(),
+                                };
 
-                    let script_params = if script.metadata().parameters().is_empty() {
-                        vec![]
-                    } else {
-                        let mut script_params = vec![];
-
-                        // First check if we have a preset set. If not, we can check each param.
-                        let preset_key = format!("{}:{}:preset", game.key(), option.key);
-                        let preset_value = settings.launch_options.get(&preset_key);
-                        let preset = if let Some(preset_value) = preset_value {
-                            if preset_value != "none" && sql_folder_presets.is_dir() {
-                                files_from_subdir(&sql_folder_presets, false)
-                                    .unwrap()
-                                    .iter()
-                                    .filter_map(|x| Preset::read(x).ok())
-                                    .find(|x| x.key() == preset_value)
-                            } else {
-                                None
+                                parameters.push((param.key().to_owned(), value));
                             }
-                        } else {
-                            None
-                        };
 
-                        match preset {
-                            Some(preset) => {
-                                for param in script.metadata().parameters() {
-                                    match preset.params().get(param.key()) {
-                                        Some(value) => script_params.push(value.to_string()),
-                                        None => {
-                                            script_params.push(param.default_value().to_string())
-                                        }
-                                    }
-                                }
-                            }
-                            None => {
-                                for param in script.metadata().parameters() {
-                                    let key =
-                                        format!("{}:{}:{}", game.key(), option.key, param.key());
-                                    if let Some(value) = settings.launch_options.get(&key) {
-                                        script_params.push(value.clone());
-                                    }
+                            preset_used = Some(preset.key().to_owned());
+                        }
+                        None => {
+                            for param in script.metadata().parameters() {
+                                let key = format!("{}:{}:{}", game.key(), option.key, param.key());
+                                if let Some(value) = settings.launch_options.get(&key) {
+                                    parameters.push((param.key().to_owned(), value.clone()));
                                 }
                             }
                         }
-
-                        script_params
-                    };
-
-                    // When there's a collision, default to the local script path.
-                    let script_name = format!("{}.yml", script.metadata().key());
-                    let local_script_path = sql_folder_local.join(&script_name);
-                    let extracted_script_path = sql_folder_extracted.join(&script_name);
-                    let remote_script_path = sql_folder_remote.join(&script_name);
-                    let script_path = if PathBuf::from(&local_script_path).is_file() {
-                        local_script_path
-                    } else if PathBuf::from(&extracted_script_path).is_file() {
-                        extracted_script_path
-                    } else {
-                        remote_script_path
-                    };
-
-                    if script_params.is_empty() {
-                        cmd.arg(script_path);
-                    } else {
-                        cmd.arg(format!(
-                            "{};{}",
-                            script_path.to_string_lossy().to_string().replace("\\", "/"),
-                            script_params.join(";")
-                        ));
                     }
-                });
-
-            #[cfg(target_os = "windows")]
-            cmd.creation_flags(DETACHED_PROCESS);
-
-            let mut h = cmd
-                .spawn()
-                .map_err(|err| anyhow!("Error when preparing the game patch: {}", err))?;
-            if let Ok(status) = h.wait() {
-                if !status.success() {
-                    return Err(anyhow!(
-                        "Something failed while creating the load order patch. Check the patcher terminal to see what happened."
-                    ));
                 }
-            }
-        }
 
-        Ok(())
+                ActiveSqlScript {
+                    key: option.key.to_owned(),
+                    name: option.name.to_owned(),
+                    path: path.to_path_buf(),
+                    preset: preset_used,
+                    parameters,
+                }
+            })
+            .collect())
     }
 
     fn generate_generic_options() -> Vec<LaunchOption> {
@@ -441,6 +571,10 @@ impl LaunchOptions {
                 parameters: vec![],
                 is_script: false,
                 presets: vec![],
+                source: None,
+                shadowed_sources: vec![],
+                origin_mod: None,
+                user_disabled: false,
             },
             LaunchOption {
                 key: "skip_intros".to_string(),
@@ -449,6 +583,10 @@ impl LaunchOptions {
                 parameters: vec![],
                 is_script: false,
                 presets: vec![],
+                source: None,
+                shadowed_sources: vec![],
+                origin_mod: None,
+                user_disabled: false,
             },
             LaunchOption {
                 key: "remove_trait_limit".to_string(),
@@ -457,6 +595,10 @@ impl LaunchOptions {
                 parameters: vec![],
                 is_script: false,
                 presets: vec![],
+                source: None,
+                shadowed_sources: vec![],
+                origin_mod: None,
+                user_disabled: false,
             },
             LaunchOption {
                 key: "remove_siege_attacker".to_string(),
@@ -465,6 +607,10 @@ impl LaunchOptions {
                 parameters: vec![],
                 is_script: false,
                 presets: vec![],
+                source: None,
+                shadowed_sources: vec![],
+                origin_mod: None,
+                user_disabled: false,
             },
             LaunchOption {
                 key: "enable_translations".to_string(),
@@ -478,6 +624,10 @@ impl LaunchOptions {
                 }],
                 is_script: false,
                 presets: vec![],
+                source: None,
+                shadowed_sources: vec![],
+                origin_mod: None,
+                user_disabled: false,
             },
             LaunchOption {
                 key: "unit_multiplier".to_string(),
@@ -491,6 +641,10 @@ impl LaunchOptions {
                 }],
                 is_script: false,
                 presets: vec![],
+                source: None,
+                shadowed_sources: vec![],
+                origin_mod: None,
+                user_disabled: false,
             },
             LaunchOption {
                 key: "universal_rebalancer".to_string(),
@@ -504,6 +658,10 @@ impl LaunchOptions {
                 }],
                 is_script: false,
                 presets: vec![],
+                source: None,
+                shadowed_sources: vec![],
+                origin_mod: None,
+                user_disabled: false,
             },
             LaunchOption {
                 key: "enable_dev_only_ui".to_string(),
@@ -512,6 +670,10 @@ impl LaunchOptions {
                 parameters: vec![],
                 is_script: false,
                 presets: vec![],
+                source: None,
+                shadowed_sources: vec![],
+                origin_mod: None,
+                user_disabled: false,
             },
         ]
     }
@@ -524,24 +686,19 @@ impl LaunchOptions {
     ) -> Result<Vec<LaunchOption>> {
         self.options.clear();
         self.scripts.clear();
+        self.script_paths.clear();
+        self.script_parse_errors.clear();
 
+        // First we need to generate the generic options that affect most games. These are always available, even without a valid game path.
+        let game_options = supported_options(game.key());
+        let mut default_options = Self::generate_generic_options();
+        default_options.retain(|option| game_options.contains(&option.key.as_str()));
+        self.options.extend_from_slice(&default_options);
+
+        // The rest of the options depend on the game files, so they need a valid game path.
         let path_is_valid =
             game_path.exists() && game_path.is_dir() && !game_path.to_string_lossy().is_empty();
         if path_is_valid {
-            // First we need to generate the generic options that affect most games.
-            let game_options = SUPPORTED_OPTIONS
-                .iter()
-                .find(|(game_key, _)| game.key() == *game_key)
-                .unwrap()
-                .1;
-            let mut default_options = Self::generate_generic_options();
-            default_options.retain(|option| {
-                game_options
-                    .iter()
-                    .any(|game_option| *game_option == option.key)
-            });
-            self.options.extend_from_slice(&default_options);
-
             // The translations one needs to be populated only with the downloaded game languages.
             if let Some(ref mut translations_option) = self
                 .options
@@ -553,23 +710,11 @@ impl LaunchOptions {
                     .iter_mut()
                     .find(|param| param.key == "language")
                 {
-                    if let Ok(ca_packs) = game.ca_packs_paths(game_path) {
-                        let mut languages = ca_packs
-                            .iter()
-                            .filter_map(|path| path.file_stem())
-                            .filter(|name| name.to_string_lossy().starts_with("local_"))
-                            .map(|name| name.to_string_lossy().split_at(6).1.to_uppercase())
-                            .collect::<Vec<_>>();
-
-                        // Sort, and remove anything longer than 2 characters to avoid duplicates.
-                        languages.retain(|lang| lang.chars().count() == 2);
-                        languages.sort();
-
-                        if !languages.is_empty() {
-                            language_param.value =
-                                LaunchOptionValue::Select(languages[0].clone(), languages.clone());
-                            language_param.default = LaunchOptionValue::Text(languages[0].clone());
-                        }
+                    let languages = Self::available_languages(game, game_path);
+                    if !languages.is_empty() {
+                        language_param.value =
+                            LaunchOptionValue::Select(languages[0].clone(), languages.clone());
+                        language_param.default = LaunchOptionValue::Text(languages[0].clone());
                     }
                 }
             }
@@ -585,86 +730,29 @@ impl LaunchOptions {
                     .iter_mut()
                     .find(|param| param.key == "base_mod")
                 {
-                    let mut load_order = GAME_LOAD_ORDER.read().unwrap().clone();
-                    let game_config = GAME_CONFIG.lock().unwrap().clone();
-
-                    if let Some(mut game_config) = game_config {
-                        if let Ok(game_data_path) = game.data_path(game_path) {
-                            load_order.update(app, &mut game_config, game, &game_data_path);
-
-                            let mut packs_for_rebalancer = load_order
-                                .packs()
-                                .iter()
-                                .filter_map(|(key, pack)| {
-                                    if !pack
-                                        .files_by_type_and_paths(
-                                            &[FileType::DB],
-                                            &[ContainerPath::Folder(
-                                                "db/land_units_tables/".to_owned(),
-                                            )],
-                                            true,
-                                        )
-                                        .is_empty()
-                                    {
-                                        Some(key.to_owned())
-                                    } else {
-                                        None
-                                    }
-                                })
-                                .collect::<Vec<_>>();
-
-                            packs_for_rebalancer.sort();
-
-                            if !packs_for_rebalancer.is_empty() {
-                                base_mod_param.value = LaunchOptionValue::Select(
-                                    packs_for_rebalancer[0].clone(),
-                                    packs_for_rebalancer.clone(),
-                                );
-                                base_mod_param.default =
-                                    LaunchOptionValue::Text(packs_for_rebalancer[0].clone());
-                            }
+                    if let Ok(game_data_path) = game.data_path(game_path) {
+                        let packs_for_rebalancer = Self::rebalancer_candidates_cached(
+                            &mut self.rebalancer_candidates_cache,
+                            app,
+                            game,
+                            &game_data_path,
+                        );
+
+                        if !packs_for_rebalancer.is_empty() {
+                            base_mod_param.value = LaunchOptionValue::Select(
+                                packs_for_rebalancer[0].clone(),
+                                packs_for_rebalancer.clone(),
+                            );
+                            base_mod_param.default =
+                                LaunchOptionValue::Text(packs_for_rebalancer[0].clone());
                         }
                     }
                 }
             }
 
-            // Scripts are done in a separate step, because they're dynamic. Priority is:
-            // - Local scripts.
-            // - Extracted scripts.
-            // - Remote scripts.
-            let extracted_scripts_folder = sql_scripts_extracted_twpatcher_path(app)?;
+            // Scripts are done in a separate step, because they're dynamic.
             let presets_folder = sql_presets_extracted_twpatcher_path(app)?;
 
-            let local_folder = sql_scripts_local_path(app)?;
-            let remote_folder = sql_scripts_remote_path(app)?;
-            let mut sql_script_paths = files_from_subdir(&local_folder.join(game.key()), false)?;
-
-            // Only add extracted paths if they don't collide with local paths, as local paths take priority.
-            if let Ok(extracted_files) = files_from_subdir(&extracted_scripts_folder, false) {
-                for extracted_file in &extracted_files {
-                    if let Ok(relative_path) =
-                        extracted_file.strip_prefix(&extracted_scripts_folder)
-                    {
-                        if !local_folder.join(relative_path).is_file() {
-                            sql_script_paths.push(extracted_file.to_path_buf());
-                        }
-                    }
-                }
-            }
-
-            // Only add remote paths if they don't collide with local or extracted paths, as they take priority.
-            if let Ok(remote_files) = files_from_subdir(&remote_folder.join(game.key()), false) {
-                for remote_file in &remote_files {
-                    if let Ok(relative_path) = remote_file.strip_prefix(&remote_folder) {
-                        if !local_folder.join(relative_path).is_file()
-                            && !extracted_scripts_folder.join(relative_path).is_file()
-                        {
-                            sql_script_paths.push(remote_file.to_path_buf());
-                        }
-                    }
-                }
-            }
-
             let presets = files_from_subdir(&presets_folder, false)
                 .unwrap_or_default()
                 .iter()
@@ -682,112 +770,160 @@ impl LaunchOptions {
                 }
             }
 
-            for path in sql_script_paths {
-                if let Some(extension) = path.extension() {
+            let extracted_path = sql_scripts_extracted_path(app)?;
+            let disabled_mod_scripts = SETTINGS
+                .read()
+                .unwrap()
+                .disabled_mod_scripts
+                .get(game.key())
+                .cloned()
+                .unwrap_or_default();
+
+            // Sources are checked in priority order, so the first script found for a key is the one used.
+            for (source, folder) in script_sources(app, game)? {
+                let mut paths = files_from_subdir(&folder, false).unwrap_or_default();
+                paths.sort();
+
+                for path in paths {
                     // Only load yml files.
-                    if extension == "yml" {
-                        if let Ok(script) = SQLScript::from_path(&path) {
-                            let presets = presets_by_script
-                                .get(script.metadata().key())
-                                .map(|x| x.iter().map(|x| x.key().to_owned()).collect())
-                                .unwrap_or_default();
-
-                            let mut params = vec![];
-                            for param in script.metadata().parameters() {
-                                params.push(LaunchOptionParameter {
-                                    key: param.key().to_string(),
-                                    name: param.name().to_string(),
-                                    value: LaunchOptionValue::Select(String::new(), vec![]),
-                                    default: match param.r#type() {
-                                        ParamType::Bool => LaunchOptionValue::Boolean(
-                                            param
-                                                .default_value()
-                                                .parse::<bool>()
-                                                .unwrap_or_default(),
-                                        ),
-                                        ParamType::Integer => LaunchOptionValue::Number(
-                                            param.default_value().parse::<i32>().unwrap_or_default()
-                                                as f64,
-                                        ),
-                                        ParamType::Float => LaunchOptionValue::Number(
-                                            param.default_value().parse::<f32>().unwrap_or_default()
-                                                as f64,
-                                        ),
-                                    },
-                                });
-                            }
+                    if path.extension().is_none_or(|extension| extension != "yml") {
+                        continue;
+                    }
 
-                            let option = LaunchOption {
-                                key: script.metadata().key().to_string(),
-                                name: script.metadata().name().to_string(),
-                                enabled: false,
-                                parameters: params,
-                                is_script: true,
-                                presets,
-                            };
-
-                            self.options.push(option);
-                            self.scripts
-                                .insert(script.metadata().key().to_string(), script);
+                    let script = match SQLScript::from_path(&path) {
+                        Ok(script) => script,
+                        Err(error) => {
+                            self.script_parse_errors.push(ScriptParseError {
+                                path,
+                                source,
+                                error: error.to_string(),
+                            });
+                            continue;
                         }
-                    }
-                }
-            }
+                    };
 
-            // Set the state of default options.
-            let settings = SETTINGS.read().unwrap().clone();
-            for option in &mut self.options {
-                let key = format!("{}:{}", game.key(), option.key);
-                if let Some(value) = settings.launch_options.get(&key) {
-                    if let Ok(value) = value.parse::<bool>() {
-                        option.enabled = value;
+                    let key = script.metadata().key().to_string();
+                    if let Some(option) = self
+                        .options
+                        .iter_mut()
+                        .find(|option| option.is_script && option.key == key)
+                    {
+                        if !option.shadowed_sources.contains(&source) {
+                            option.shadowed_sources.push(source);
+                        }
+                        continue;
                     }
 
-                    for param in &mut option.parameters {
-                        let key = format!("{}:{}", key, param.key);
-                        if let Some(value) = settings.launch_options.get(&key) {
-                            param.value = match param.value {
-                                LaunchOptionValue::Boolean(_) => LaunchOptionValue::Boolean(
-                                    value.parse::<bool>().unwrap_or_default(),
+                    let origin_mod = match source {
+                        ScriptSource::Extracted => extracted_script_key(&extracted_path, &path)
+                            .and_then(|path| {
+                                GAME_LOAD_ORDER
+                                    .read()
+                                    .unwrap()
+                                    .extracted_scripts()
+                                    .get(&path)
+                                    .cloned()
+                            }),
+                        _ => None,
+                    };
+                    let user_disabled = origin_mod.is_some() && disabled_mod_scripts.contains(&key);
+
+                    let presets = presets_by_script
+                        .get(&key)
+                        .map(|x| x.iter().map(|x| x.key().to_owned()).collect())
+                        .unwrap_or_default();
+
+                    let mut params = vec![];
+                    for param in script.metadata().parameters() {
+                        params.push(LaunchOptionParameter {
+                            key: param.key().to_string(),
+                            name: param.name().to_string(),
+                            value: LaunchOptionValue::Select(String::new(), vec![]),
+                            default: match param.r#type() {
+                                ParamType::Bool => LaunchOptionValue::Boolean(
+                                    param.default_value().parse::<bool>().unwrap_or_default(),
                                 ),
-                                LaunchOptionValue::Number(_) => LaunchOptionValue::Number(
-                                    value.parse::<f64>().unwrap_or_default(),
+                                ParamType::Integer => LaunchOptionValue::Number(
+                                    param.default_value().parse::<i32>().unwrap_or_default() as f64,
                                 ),
-                                LaunchOptionValue::Text(_) => {
-                                    LaunchOptionValue::Text(value.clone())
-                                }
-                                LaunchOptionValue::Select(_, _) => {
-                                    LaunchOptionValue::Select(value.clone(), vec![])
-                                }
-                            };
-                        } else {
-                            param.value = match &param.default {
-                                LaunchOptionValue::Boolean(default) => {
-                                    LaunchOptionValue::Boolean(*default)
-                                }
-                                LaunchOptionValue::Number(default) => {
-                                    LaunchOptionValue::Number(*default)
-                                }
-                                LaunchOptionValue::Text(default) => match &param.value {
-                                    LaunchOptionValue::Select(_, available_values) => {
-                                        if available_values.contains(default) {
-                                            LaunchOptionValue::Select(
-                                                default.to_owned(),
-                                                available_values.clone(),
-                                            )
-                                        } else {
-                                            param.value.clone()
-                                        }
-                                    }
-                                    _ => LaunchOptionValue::Text(default.to_owned()),
-                                },
+                                ParamType::Float => LaunchOptionValue::Number(
+                                    param.default_value().parse::<f32>().unwrap_or_default() as f64,
+                                ),
+                            },
+                        });
+                    }
+
+                    let option = LaunchOption {
+                        key: key.to_owned(),
+                        name: script.metadata().name().to_string(),
+                        enabled: false,
+                        parameters: params,
+                        is_script: true,
+                        presets,
+                        source: Some(source),
+                        shadowed_sources: vec![],
+                        origin_mod,
+                        user_disabled,
+                    };
+
+                    self.options.push(option);
+                    self.scripts.insert(key.to_owned(), script);
+                    self.script_paths.insert(key, path);
+                }
+            }
+        }
+
+        // Set the state of default options.
+        let settings = SETTINGS.read().unwrap().clone();
+        for option in &mut self.options {
+            let key = format!("{}:{}", game.key(), option.key);
+            if let Some(value) = settings.launch_options.get(&key) {
+                if let Ok(value) = value.parse::<bool>() {
+                    option.enabled = value;
+                }
 
-                                // All default selects are just one text value. So this should never happen.
-                                LaunchOptionValue::Select(default, _) => {
-                                    LaunchOptionValue::Select(default.to_owned(), vec![])
+                for param in &mut option.parameters {
+                    let key = format!("{}:{}", key, param.key);
+                    if let Some(value) = settings.launch_options.get(&key) {
+                        param.value = match param.value {
+                            LaunchOptionValue::Boolean(_) => LaunchOptionValue::Boolean(
+                                value.parse::<bool>().unwrap_or_default(),
+                            ),
+                            LaunchOptionValue::Number(_) => {
+                                LaunchOptionValue::Number(value.parse::<f64>().unwrap_or_default())
+                            }
+                            LaunchOptionValue::Text(_) => LaunchOptionValue::Text(value.clone()),
+                            LaunchOptionValue::Select(_, _) => {
+                                LaunchOptionValue::Select(value.clone(), vec![])
+                            }
+                        };
+                    } else {
+                        param.value = match &param.default {
+                            LaunchOptionValue::Boolean(default) => {
+                                LaunchOptionValue::Boolean(*default)
+                            }
+                            LaunchOptionValue::Number(default) => {
+                                LaunchOptionValue::Number(*default)
+                            }
+                            LaunchOptionValue::Text(default) => match &param.value {
+                                LaunchOptionValue::Select(_, available_values) => {
+                                    if available_values.contains(default) {
+                                        LaunchOptionValue::Select(
+                                            default.to_owned(),
+                                            available_values.clone(),
+                                        )
+                                    } else {
+                                        param.value.clone()
+                                    }
                                 }
-                            };
-                        }
+                                _ => LaunchOptionValue::Text(default.to_owned()),
+                            },
+
+                            // All default selects are just one text value. So this should never happen.
+                            LaunchOptionValue::Select(default, _) => {
+                                LaunchOptionValue::Select(default.to_owned(), vec![])
+                            }
+                        };
                     }
                 }
             }
@@ -795,6 +931,162 @@ impl LaunchOptions {
 
         Ok(self.options.clone())
     }
+
+    /// This function returns the packs that can be used as base mod for the universal rebalancer,
+    /// which are the enabled packs with a land_units table.
+    ///
+    /// The result is cached until the load order changes.
+    pub fn rebalancer_candidates(
+        &mut self,
+        app: &AppHandle,
+        game: &GameInfo,
+        game_data_path: &Path,
+    ) -> Vec<String> {
+        Self::rebalancer_candidates_cached(
+            &mut self.rebalancer_candidates_cache,
+            app,
+            game,
+            game_data_path,
+        )
+    }
+
+    fn rebalancer_candidates_cached(
+        cache: &mut Option<(String, Vec<String>, Vec<String>)>,
+        app: &AppHandle,
+        game: &GameInfo,
+        game_data_path: &Path,
+    ) -> Vec<String> {
+        let mut load_order = GAME_LOAD_ORDER.read().unwrap().clone();
+        let cache_key = Self::rebalancer_cache_key(&load_order);
+        if let Some((game_key, mods, candidates)) = cache.as_ref() {
+            if game_key == game.key() && *mods == cache_key {
+                return candidates.clone();
+            }
+        }
+
+        let game_config = GAME_CONFIG.lock().unwrap().clone();
+        let mut packs_for_rebalancer = vec![];
+
+        if let Some(game_config) = game_config {
+            load_order.update(app, &game_config, game, game_data_path);
+
+            packs_for_rebalancer = load_order
+                .packs()
+                .iter()
+                .filter_map(|(key, pack)| {
+                    if !pack
+                        .files_by_type_and_paths(
+                            &[FileType::DB],
+                            &[ContainerPath::Folder("db/land_units_tables/".to_owned())],
+                            true,
+                        )
+                        .is_empty()
+                    {
+                        Some(key.to_owned())
+                    } else {
+                        None
+                    }
+                })
+                .collect::<Vec<_>>();
+
+            packs_for_rebalancer.sort();
+
+            *cache = Some((
+                game.key().to_owned(),
+                cache_key,
+                packs_for_rebalancer.clone(),
+            ));
+        }
+
+        packs_for_rebalancer
+    }
+
+    fn rebalancer_cache_key(load_order: &LoadOrder) -> Vec<String> {
+        load_order
+            .mods()
+            .iter()
+            .chain(load_order.movies().iter())
+            .cloned()
+            .collect()
+    }
+
+    /// This function returns the languages the translations option can use, based on the localisation packs of the game.
+    pub fn available_languages(game: &GameInfo, game_path: &Path) -> Vec<String> {
+        match game.ca_packs_paths(game_path) {
+            Ok(ca_packs) => {
+                let mut languages = ca_packs
+                    .iter()
+                    .filter_map(|path| path.file_stem())
+                    .filter_map(|name| language_from_pack_stem(&name.to_string_lossy()))
+                    .collect::<Vec<_>>();
+
+                // Multiple packs per language are common (patches, split packs...), so remove duplicates.
+                languages.sort();
+                languages.dedup();
+                languages
+            }
+            Err(_) => vec![],
+        }
+    }
+}
+
+/// Returns the keys of the launch options supported by a game. Unknown games support none.
+pub fn supported_options(game_key: &str) -> &'static [&'static str] {
+    SUPPORTED_OPTIONS
+        .iter()
+        .find(|(key, _)| game_key == *key)
+        .map(|(_, options)| *options)
+        .unwrap_or_default()
+}
+
+/// This function returns the path the temp pack will be generated at, and the folder to add as working directory for it, if any.
+fn temp_pack_path(
+    app: &AppHandle,
+    game: &GameInfo,
+    data_path: &Path,
+) -> Result<(PathBuf, Option<PathBuf>)> {
+    // We need to use an alternative name for Shogun 2, Rome 2, Attila and Thrones because their load order logic for movie packs seems... either different or broken.
+    let reserved_pack_name = if game.key() == KEY_SHOGUN_2
+        || game.key() == KEY_ROME_2
+        || game.key() == KEY_ATTILA
+        || game.key() == KEY_THRONES_OF_BRITANNIA
+    {
+        RESERVED_PACK_NAME_ALTERNATIVE
+    } else {
+        RESERVED_PACK_NAME
+    };
+
+    // Support for add_working_directory seems to be only present in rome 2 and newer games. For older games, we drop the pack into /data.
+    if *game.raw_db_version() >= 1 {
+        let temp_packs_folder = temp_packs_folder(app, game)?;
+        Ok((
+            temp_packs_folder.join(reserved_pack_name),
+            Some(temp_packs_folder),
+        ))
+    } else {
+        Ok((data_path.join(reserved_pack_name), None))
+    }
+}
+
+/// This function returns the folders SQL scripts are loaded from for the provided game, in order of priority:
+/// - Local scripts.
+/// - Extracted scripts.
+/// - Remote scripts.
+fn script_sources(app: &AppHandle, game: &GameInfo) -> Result<Vec<(ScriptSource, PathBuf)>> {
+    Ok(vec![
+        (
+            ScriptSource::Local,
+            sql_scripts_local_path(app)?.join(game.key()),
+        ),
+        (
+            ScriptSource::Extracted,
+            sql_scripts_extracted_twpatcher_path(app)?,
+        ),
+        (
+            ScriptSource::Remote,
+            sql_scripts_remote_path(app)?.join(game.key()),
+        ),
+    ])
 }
 
 fn load_order_file_name(game: &GameInfo) -> String {
@@ -806,3 +1098,62 @@ fn load_order_file_name(game: &GameInfo) -> String {
         USER_SCRIPT_FILE_NAME.to_string()
     }
 }
+
+/// This function returns the language code of a localisation pack, from its file stem.
+///
+/// Language codes are the alphabetic segments right after `local_`, like `local_en` or `local_zh_cn`.
+/// Anything after them (`local_en_patch`, `local_en_2`...) is ignored, so all packs of a language return the same code.
+fn language_from_pack_stem(stem: &str) -> Option<String> {
+    let code = stem.strip_prefix("local_")?;
+    let mut segments = code.split('_');
+
+    let language = segments.next()?;
+    if !(2..=3).contains(&language.len()) || !language.chars().all(|c| c.is_ascii_alphabetic()) {
+        return None;
+    }
+
+    let mut language = language.to_uppercase();
+    if let Some(region) = segments.next() {
+        if region.len() == 2 && region.chars().all(|c| c.is_ascii_alphabetic()) {
+            language.push('_');
+            language.push_str(&region.to_uppercase());
+        }
+    }
+
+    Some(language)
+}
+
+/// This function splits a string of command line arguments on whitespace, keeping quoted arguments together.
+fn split_args(args: &str) -> Vec<String> {
+    let mut split = vec![];
+    let mut current = String::new();
+    let mut quote = None;
+    let mut in_arg = false;
+
+    for c in args.chars() {
+        match quote {
+            Some(q) if c == q => quote = None,
+            Some(_) => current.push(c),
+            None if c == '"' || c == '\'' => {
+                quote = Some(c);
+                in_arg = true;
+            }
+            None if c.is_whitespace() => {
+                if in_arg {
+                    split.push(std::mem::take(&mut current));
+                    in_arg = false;
+                }
+            }
+            None => {
+                current.push(c);
+                in_arg = true;
+            }
+        }
+    }
+
+    if in_arg {
+        split.push(current);
+    }
+
+    split
+}
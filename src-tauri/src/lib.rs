@@ -2,25 +2,38 @@ use anyhow::anyhow;
 use base64::prelude::BASE64_STANDARD;
 use tauri::{Emitter, Listener, Manager};
 
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::fs::DirBuilder;
 use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::{Arc, LazyLock, Mutex, RwLock};
+use std::time::{Instant, UNIX_EPOCH};
 
-use rpfm_lib::files::pack::Pack;
+use rpfm_lib::files::{Container, pack::Pack};
 use rpfm_lib::games::{GameInfo, pfh_file_type::PFHFileType, supported_games::*};
+use rpfm_lib::integrations::log::error;
 use rpfm_lib::schema::Schema;
-use rpfm_lib::utils::path_to_absolute_string;
+use rpfm_lib::utils::{path_to_absolute_path, path_to_absolute_string};
 
 use crate::frontend_types::*;
 use crate::launch_options::*;
 use crate::mod_manager::game_config::GameConfig;
-use crate::mod_manager::integrations::{Integrations, RemoteMetadata, StoreId};
+use crate::mod_manager::integrations::{
+    GameLockDetails, Integrations, RemoteMetadata, StoreId, TxStoreResponse,
+};
 use crate::mod_manager::load_order::{
-    CUSTOM_MOD_LIST_FILE_NAME, LoadOrder, LoadOrderDirectionMove,
+    CUSTOM_MOD_LIST_FILE_NAME, ImportedLoadOrderMode, LoadOrder, LoadOrderDirectionMove,
+    ShareableLoadOrder, load_order_path_string,
 };
+use crate::mod_manager::mods::{Mod, ModLocation, ShareableMod};
 use crate::mod_manager::profiles::Profile;
-use crate::mod_manager::{SECONDARY_FOLDER_NAME, secondary_mods_path};
+use crate::mod_manager::snapshots::Snapshot;
+use crate::mod_manager::{
+    copy_to_data, copy_to_secondary, ensure_game_folders, game_supports_content_loading,
+    game_supports_custom_mod_list, game_supports_extra_launch_args, game_supports_secondary_folder,
+    game_uses_movie_masking, generate_movie_masks, is_same_volume, move_to_secondary,
+    movies_to_mask, secondary_mods_path,
+};
 use crate::settings::*;
 
 mod frontend_types;
@@ -62,6 +75,16 @@ static GAME_SELECTED: LazyLock<Arc<RwLock<GameInfo>>> = LazyLock::new(|| {
 static INTEGRATIONS: LazyLock<Arc<Mutex<Integrations>>> =
     LazyLock::new(|| Arc::new(Mutex::new(Integrations::new())));
 
+/// Timings of the last game load, for diagnosing slow loads.
+static LAST_LOAD_TIMINGS: LazyLock<Arc<RwLock<Option<LoadTimings>>>> =
+    LazyLock::new(|| Arc::new(RwLock::new(None)));
+
+/// If mutating commands are blocked, so the current setup can be shown without risk of changing it.
+static VIEW_ONLY: AtomicBool = AtomicBool::new(false);
+
+/// If the game config or load order have changes not yet saved to disk, due to autosave being disabled.
+static UNSAVED_CHANGES: AtomicBool = AtomicBool::new(false);
+
 const VERSION: &str = env!("CARGO_PKG_VERSION");
 const VERSION_SUBTITLE: &str = " -- When I learned maths";
 
@@ -79,6 +102,9 @@ const REPO_NAME: &str = "runcher";
 const RESERVED_PACK_NAME: &str = "zzzzzzzzzzzzzzzzzzzzrun_you_fool_thron.pack";
 const RESERVED_PACK_NAME_ALTERNATIVE: &str = "!!!!!!!!!!!!!!!!!!!!!run_you_fool_thron.pack";
 
+/// Max size of a load order downloaded from a link. Real ones are a few KB at most.
+const IMPORTED_LOAD_ORDER_MAX_SIZE: u64 = 1024 * 1024;
+
 #[tauri::command]
 async fn launch_game(
     app: tauri::AppHandle,
@@ -87,71 +113,14 @@ async fn launch_game(
 ) -> Result<String, String> {
     use base64::Engine;
 
-    let mut folder_list = String::new();
-    let mut pack_list = String::new();
-
     let game = GAME_SELECTED.read().unwrap().clone();
     let game_path = SETTINGS
         .read()
         .unwrap()
         .game_path(&game)
         .map_err(|e| format!("Error getting the game's path: {}", e))?;
-    let data_path = game
-        .data_path(&game_path)
-        .map_err(|e| format!("Error getting the game's data path: {}", e))?;
-    let game_config = GAME_CONFIG.lock().unwrap().clone().unwrap();
-    let load_order = GAME_LOAD_ORDER.read().unwrap().clone();
-
-    load_order.build_load_order_string(
-        &app,
-        &game_config,
-        &game,
-        &data_path,
-        &mut pack_list,
-        &mut folder_list,
-    );
-
-    // If our folder list contains the secondary folder, we need to make sure we create the masks folder in it,
-    // and mask in there all non-enabled movie files. Note that we only use this in games older than warhammer. Newer games use the exclude_pack_file command.
-    if *game.raw_db_version() <= 1
-        || (*game.raw_db_version() == 2
-            && (game.key() == KEY_ROME_2
-                || game.key() == KEY_ATTILA
-                || game.key() == KEY_THRONES_OF_BRITANNIA))
-    {
-        let secondary_mods_path =
-            secondary_mods_path(&app, game.key()).unwrap_or_else(|_| PathBuf::new());
-        let secondary_mods_path_str = path_to_absolute_string(&secondary_mods_path);
-
-        if secondary_mods_path.is_dir() && folder_list.contains(&secondary_mods_path_str) {
-            let masks_path = secondary_mods_path.join(SECONDARY_FOLDER_NAME);
-
-            // Remove all files in it so previous maskings do not interfere.
-            if masks_path.is_dir() {
-                let _ = std::fs::remove_dir_all(&masks_path);
-            }
-
-            let _ = DirBuilder::new().recursive(true).create(&masks_path);
-
-            let mut mask_pack =
-                Pack::new_with_version(game.pfh_version_by_file_type(PFHFileType::Movie));
-            mask_pack.set_pfh_file_type(PFHFileType::Movie);
-
-            for path in std::fs::read_dir(secondary_mods_path)
-                .map_err(|e| format!("Error reading the secondary mods path: {}", e))?
-            {
-                let file_name = path.unwrap().file_name().to_string_lossy().to_string();
 
-                if let Some(modd) = game_config.mods().get(&file_name) {
-                    if modd.pack_type() == &PFHFileType::Movie && !modd.enabled(&game, &data_path) {
-                        mask_pack
-                            .save(Some(&masks_path.join(file_name)), &game, &None)
-                            .map_err(|e| format!("Error saving the mask pack: {}", e))?;
-                    }
-                }
-            }
-        }
-    }
+    let (file_path, _) = prepare_launch_files(&app, &launch_options)?;
 
     // Check if we are loading a save. First option is no save load. Any index above that is a save.
     let mut extra_args: Vec<String> = vec![];
@@ -164,57 +133,64 @@ async fn launch_game(
         }
     }*/
 
-    let file_path = LoadOrder::path_as_load_order_file(&game, &game_path)
-        .map_err(|e| format!("Error getting the load order file path: {}", e))?;
-
-    // Setup the launch options stuff. This may add a line to the folder list, so we need to resave the load order file after this.
-    let folder_list_pre = folder_list.to_owned();
-    LoadOrder::save_as_load_order_file(&file_path, &game, &folder_list, &pack_list)
-        .map_err(|e| format!("Error saving the load order file: {}", e))?;
-    LAUNCH_OPTIONS
-        .write()
-        .unwrap()
-        .prepare_launch_options(&app, &launch_options, &game, &data_path, &mut folder_list)
-        .map_err(|e| format!("Error preparing launch options: {}", e))?;
-
-    if folder_list != folder_list_pre {
-        LoadOrder::save_as_load_order_file(&file_path, &game, &folder_list, &pack_list)
-            .map_err(|e| format!("Error saving the load order file: {}", e))?;
-    }
-
     // Launch is done through workshopper to getup the Steam Api.
     //
     // Here we just build the commands and pass them to workshopper.
     match game.executable_path(&game_path) {
         Some(exec_game) => {
-            let command = if cfg!(target_os = "windows") {
-                let mut command = format!(
+            // Custom load order file is only supported by Shogun 2 and later games.
+            let mod_list = if game_supports_custom_mod_list(&game) {
+                CUSTOM_MOD_LIST_FILE_NAME.to_owned()
+            } else {
+                load_order_path_string(&file_path)
+            };
+
+            let mut command = if cfg!(target_os = "windows") {
+                format!(
                     "cmd /C start /W /d \"{}\" \"{}\" \"{}\";",
-                    game_path.to_string_lossy().replace('\\', "/"),
+                    load_order_path_string(&game_path),
                     exec_game.file_name().unwrap().to_string_lossy(),
-                    // Custom load order file is only supported by Shogun 2 and later games.
-                    if *game.raw_db_version() >= 1 {
-                        CUSTOM_MOD_LIST_FILE_NAME.to_owned()
-                    } else {
-                        file_path.to_string_lossy().replace('\\', "/")
-                    }
-                );
-
-                // Only Shogun 2 and later games support extra arguments.
-                if *game.raw_db_version() >= 1 {
-                    for arg in &extra_args {
-                        command.push(' ');
-                        command.push_str(arg);
-                    }
-                }
-
-                command
+                    mod_list
+                )
             } else if cfg!(target_os = "linux") {
-                return Err(format!("Unsupported OS."));
+                // Games without a native port only have a windows executable, which needs to go through Proton.
+                // We can't call Proton directly, so we let Steam launch it. It already uses the game folder as working dir.
+                //
+                // The shell takes the ; as a command separator, so it goes inside the quotes to reach the game.
+                let is_windows_exe = exec_game
+                    .extension()
+                    .is_some_and(|extension| extension.eq_ignore_ascii_case("exe"));
+                if is_windows_exe {
+                    let steam_id = game
+                        .steam_id(&game_path)
+                        .map_err(|e| format!("Error getting the game's steam id: {}", e))?;
+
+                    format!(
+                        "cd \"{}\" && steam -applaunch {} \"{};\"",
+                        load_order_path_string(&game_path),
+                        steam_id,
+                        mod_list
+                    )
+                } else {
+                    format!(
+                        "cd \"{}\" && \"./{}\" \"{};\"",
+                        load_order_path_string(&game_path),
+                        exec_game.file_name().unwrap().to_string_lossy(),
+                        mod_list
+                    )
+                }
             } else {
                 return Err(format!("Unsupported OS."));
             };
 
+            // Only Shogun 2 and later games support extra arguments.
+            if game_supports_extra_launch_args(&game) {
+                for arg in &extra_args {
+                    command.push(' ');
+                    command.push_str(arg);
+                }
+            }
+
             let command = BASE64_STANDARD.encode(command);
             let integrations = INTEGRATIONS.lock().unwrap().clone();
 
@@ -232,10 +208,153 @@ async fn launch_game(
     }
 }
 
+/// Does everything launching the game does, except launching it. For checking the generated files.
+#[tauri::command]
+async fn prepare_launch(
+    app: tauri::AppHandle,
+    launch_options: Vec<LaunchOption>,
+) -> Result<LaunchPreparation, String> {
+    prepare_launch_files(&app, &launch_options).map(|(_, preparation)| preparation)
+}
+
+/// Enables or disables the view-only mode. While enabled, commands that change mods, categories or the load order fail.
+#[tauri::command]
+fn set_view_only(enabled: bool) -> Result<(), String> {
+    VIEW_ONLY.store(enabled, Ordering::SeqCst);
+    Ok(())
+}
+
+/// Saves the game config and load order to disk. Only needed if autosave is disabled.
+#[tauri::command]
+fn save_now(app: tauri::AppHandle) -> Result<(), String> {
+    save_unsaved_changes(&app).map_err(|e| format!("Error saving data: {}", e))
+}
+
+/// Saves everything we keep in memory to disk, regardless of the autosave setting.
+#[tauri::command]
+fn flush_state(app: tauri::AppHandle) -> Result<FlushStateResult, String> {
+    Ok(flush_all_state(&app))
+}
+
+/// Compares the in-memory game config and load order against the ones on disk.
+#[tauri::command]
+fn get_unsaved_changes(app: tauri::AppHandle) -> Result<UnsavedChanges, String> {
+    let game = GAME_SELECTED.read().unwrap().clone();
+    let game_path = SETTINGS
+        .read()
+        .unwrap()
+        .game_path(&game)
+        .map_err(|e| format!("Error getting the game's path: {}", e))?;
+    let game_data_path = game
+        .data_path(&game_path)
+        .map_err(|e| format!("Error getting the game's data path: {}", e))?;
+    let game_config = GAME_CONFIG
+        .lock()
+        .unwrap()
+        .clone()
+        .ok_or_else(|| "No game config loaded.".to_string())?;
+    let load_order = GAME_LOAD_ORDER.read().unwrap().clone();
+
+    let saved_game_config = GameConfig::load(&app, &game, true)
+        .map_err(|e| format!("Error loading the saved game config: {}", e))?;
+    let saved_load_order = LoadOrder::load(&app, &game).unwrap_or_default();
+
+    let mut changes = UnsavedChanges::default();
+
+    for (id, modd) in game_config.mods() {
+        let enabled = modd.enabled(&game, &game_data_path);
+        let saved_enabled = saved_game_config
+            .mods()
+            .get(id)
+            .is_some_and(|saved| saved.enabled(&game, &game_data_path));
+
+        if enabled && !saved_enabled {
+            changes.mods_enabled.push(id.to_owned());
+        } else if !enabled && saved_enabled {
+            changes.mods_disabled.push(id.to_owned());
+        }
+    }
+
+    // Only compare the relative position of mods in both lists, so adding or removing a mod doesn't mark everything after it as moved.
+    let common = load_order
+        .mods()
+        .iter()
+        .filter(|id| saved_load_order.mods().contains(id))
+        .collect::<Vec<_>>();
+    let saved_common = saved_load_order
+        .mods()
+        .iter()
+        .filter(|id| load_order.mods().contains(id))
+        .collect::<Vec<_>>();
+    changes.mods_moved = common
+        .iter()
+        .zip(saved_common.iter())
+        .filter(|(id, saved_id)| id != saved_id)
+        .map(|(id, _)| id.to_string())
+        .collect();
+
+    changes.load_order_mode_changed = load_order.automatic() != saved_load_order.automatic();
+
+    for (category, mods) in game_config.categories() {
+        match saved_game_config.categories().get(category) {
+            Some(saved_mods) if saved_mods != mods => {
+                changes.categories_changed.push(category.to_owned())
+            }
+            Some(_) => {}
+            None => changes.categories_added.push(category.to_owned()),
+        }
+    }
+
+    changes.categories_removed = saved_game_config
+        .categories()
+        .keys()
+        .filter(|category| !game_config.categories().contains_key(*category))
+        .cloned()
+        .collect();
+
+    changes.categories_reordered = game_config
+        .categories_order()
+        .iter()
+        .filter(|category| saved_game_config.categories_order().contains(category))
+        .ne(saved_game_config
+            .categories_order()
+            .iter()
+            .filter(|category| game_config.categories_order().contains(category)));
+
+    changes.mods_enabled.sort();
+    changes.mods_disabled.sort();
+    changes.categories_added.sort();
+    changes.categories_removed.sort();
+    changes.categories_changed.sort();
+
+    changes.has_changes = !changes.mods_enabled.is_empty()
+        || !changes.mods_disabled.is_empty()
+        || !changes.mods_moved.is_empty()
+        || changes.load_order_mode_changed
+        || !changes.categories_added.is_empty()
+        || !changes.categories_removed.is_empty()
+        || !changes.categories_changed.is_empty()
+        || changes.categories_reordered;
+
+    Ok(changes)
+}
+
+/// Returns how long each phase of the last game load took, if a game has been loaded.
+#[tauri::command]
+fn get_last_load_timings() -> Result<Option<LoadTimings>, String> {
+    Ok(LAST_LOAD_TIMINGS.read().unwrap().clone())
+}
+
 #[tauri::command]
 async fn get_launch_options(app: tauri::AppHandle) -> Result<Vec<LaunchOption>, String> {
     let game = GAME_SELECTED.read().unwrap().clone();
-    let game_path = SETTINGS.read().unwrap().game_path(&game).unwrap();
+
+    // Games without a path still get the options that don't depend on the game files.
+    let game_path = SETTINGS
+        .read()
+        .unwrap()
+        .game_path(&game)
+        .unwrap_or_default();
     let options = LAUNCH_OPTIONS
         .write()
         .unwrap()
@@ -244,746 +363,3871 @@ async fn get_launch_options(app: tauri::AppHandle) -> Result<Vec<LaunchOption>,
     Ok(options)
 }
 
+/// Regenerates the launch options of the current game, to pick up scripts and rebalancer candidates from newly toggled mods.
 #[tauri::command]
-fn get_sidebar_icons() -> Vec<SidebarIcon> {
-    let games = SupportedGames::default();
-    let games = games.games_sorted();
-    let mut icons = Vec::with_capacity(games.len() - 1);
-    for game in games {
-        if game.key() != KEY_ARENA {
-            icons.push(SidebarIcon {
-                id: game.key().to_string(),
-                name: game.display_name().to_string(),
-                icon: game.icon_small().to_string(),
-            });
-        }
-    }
+async fn refresh_launch_options(app: tauri::AppHandle) -> Result<Vec<LaunchOption>, String> {
+    let game = GAME_SELECTED.read().unwrap().clone();
+    let game_path = SETTINGS
+        .read()
+        .unwrap()
+        .game_path(&game)
+        .unwrap_or_default();
 
-    icons
+    let mut launch_options = LAUNCH_OPTIONS.write().unwrap();
+    launch_options.clear_rebalancer_candidates_cache();
+    launch_options
+        .generate_options(&app, &game, &game_path)
+        .map_err(|e| format!("Error generating launch options: {}", e))
 }
 
+/// Allows the user to opt out of running a SQL script shipped by a mod, without disabling the mod.
 #[tauri::command]
-async fn handle_mod_toggled(
+fn set_script_enabled(
     app: tauri::AppHandle,
-    mod_id: &str,
-    is_checked: bool,
-) -> Result<Vec<ListItem>, String> {
-    let mod_id = unescape(mod_id);
-
-    println!("Mod {} checkbox changed to: {}", mod_id, is_checked);
-
-    let game_info = GAME_SELECTED.read().unwrap().clone();
-    let game_path = SETTINGS.read().unwrap().game_path(&game_info).unwrap();
-    let mut game_config = GAME_CONFIG.lock().unwrap().clone().unwrap();
-    let mut load_order = GAME_LOAD_ORDER.read().unwrap().clone();
-
-    game_config
-        .mods_mut()
-        .get_mut(&mod_id)
+    script_key: &str,
+    enabled: bool,
+) -> Result<Vec<LaunchOption>, String> {
+    if LAUNCH_OPTIONS
+        .read()
         .unwrap()
-        .set_enabled(is_checked);
-
-    let _ = game_config
-        .update_mod_list(&app, &game_info, &game_path, &mut load_order, false)
-        .await
-        .map_err(|e| format!("Error loading data: {}", e))?;
-    let items = load_packs(&app, &game_config, &game_info, &game_path, &load_order)
-        .await
-        .map_err(|e| format!("Error loading data: {}", e))?;
+        .script_origin_mod(script_key)
+        .is_none()
+    {
+        return Err(format!("Script {} is not shipped by any mod.", script_key));
+    }
 
-    game_config
-        .save(&app, &game_info)
-        .map_err(|e| format!("Error saving data: {}", e))?;
+    let game = GAME_SELECTED.read().unwrap().clone();
+    let mut settings = SETTINGS.read().unwrap().clone();
+    let game_path = settings
+        .game_path(&game)
+        .map_err(|e| format!("Error getting the game's path: {}", e))?;
+    let disabled = settings
+        .disabled_mod_scripts
+        .entry(game.key().to_owned())
+        .or_default();
+
+    disabled.retain(|key| key != script_key);
+    if !enabled {
+        disabled.push(script_key.to_owned());
+    }
 
-    *GAME_LOAD_ORDER.write().unwrap() = load_order;
-    *GAME_CONFIG.lock().unwrap() = Some(game_config);
+    settings
+        .save(&app)
+        .map_err(|e| format!("Failed to save settings: {}", e))?;
+    *SETTINGS.write().unwrap() = settings;
 
-    Ok(items)
+    // Regenerate the options, so the script shows up as disabled.
+    LAUNCH_OPTIONS
+        .write()
+        .unwrap()
+        .generate_options(&app, &game, &game_path)
+        .map_err(|e| format!("Error generating launch options: {}", e))
 }
 
 #[tauri::command]
-fn handle_mod_category_change(
-    app: tauri::AppHandle,
-    mut mod_ids: Vec<String>,
-    category_id: &str,
-) -> Result<(), String> {
-    let mod_ids = mod_ids
-        .iter_mut()
-        .map(|id| unescape(id))
-        .collect::<Vec<String>>();
-
-    let category_id = unescape(category_id);
+fn get_script_conflicts() -> Vec<ScriptConflict> {
+    GAME_LOAD_ORDER
+        .read()
+        .unwrap()
+        .script_conflicts()
+        .iter()
+        .map(|(path, mods)| ScriptConflict {
+            path: path.to_owned(),
+            mods: mods.to_vec(),
+            winner: mods.last().cloned().unwrap_or_default(),
+        })
+        .collect()
+}
 
+/// Removes the extracted sql scripts and presets, and extracts them again from the current load order.
+///
+/// Returns the paths of the re-extracted files, relative to the extracted scripts folder.
+#[tauri::command]
+fn clear_extracted_scripts_cache(app: tauri::AppHandle) -> Result<Vec<String>, String> {
     let game_info = GAME_SELECTED.read().unwrap().clone();
-    let mut game_config = GAME_CONFIG.lock().unwrap().clone().unwrap();
-
-    // Only proceed if the category is valid.
-    if !game_config.categories().contains_key(&category_id) {
-        return Err(format!("Category {} not found", &category_id));
+    let game_path = SETTINGS
+        .read()
+        .unwrap()
+        .game_path(&game_info)
+        .map_err(|e| format!("Error getting the game's path: {}", e))?;
+    let game_data_path = game_info
+        .data_path(&game_path)
+        .map_err(|e| format!("Error getting the game's data path: {}", e))?;
+    let game_config = GAME_CONFIG
+        .lock()
+        .unwrap()
+        .clone()
+        .ok_or_else(|| "No game config loaded.".to_string())?;
+
+    // The rebuild ignores removal errors, so remove the folder here to report them.
+    let sql_path = sql_scripts_extracted_path(&app)
+        .map_err(|e| format!("Error getting the extracted scripts path: {}", e))?;
+    if sql_path.is_dir() {
+        std::fs::remove_dir_all(&sql_path)
+            .map_err(|e| format!("Error removing the extracted scripts: {}", e))?;
     }
 
-    for mods in game_config.categories_mut().values_mut() {
-        mods.retain(|x| !mod_ids.contains(x));
-    }
+    let mut load_order = GAME_LOAD_ORDER.read().unwrap().clone();
+    load_order.update(&app, &game_config, &game_info, &game_data_path);
 
-    if let Some(target_mods) = game_config.categories_mut().get_mut(&category_id) {
-        target_mods.extend(mod_ids);
-    }
+    let mut extracted = load_order
+        .extracted_scripts()
+        .keys()
+        .cloned()
+        .collect::<Vec<_>>();
+    extracted.sort();
 
-    game_config
-        .save(&app, &game_info)
-        .map_err(|e| format!("Error saving data: {}", e))?;
-    *GAME_CONFIG.lock().unwrap() = Some(game_config);
+    *GAME_LOAD_ORDER.write().unwrap() = load_order;
 
-    Ok(())
+    Ok(extracted)
 }
 
+/// Saves the conflicts between the loaded packs to a file. Format can be either json or text.
 #[tauri::command]
-fn init_settings(app_handle: tauri::AppHandle) -> Result<AppSettings, String> {
-    AppSettings::init(&app_handle).map_err(|e| format!("Failed to load settings: {}", e))
-}
+fn export_conflict_report(path: &str, format: &str) -> Result<(), String> {
+    let game = GAME_SELECTED.read().unwrap().clone();
+    let load_order = GAME_LOAD_ORDER.read().unwrap();
+    let (file_conflicts, table_conflicts) = load_order.pack_conflicts();
+    let to_conflicts = |conflicts: BTreeMap<String, Vec<String>>| {
+        conflicts
+            .into_iter()
+            .map(|(path, mods)| PackConflict {
+                // Conflicts are sorted by priority, with movie packs above the mods they override.
+                winner: mods.first().cloned().unwrap_or_default(),
+                path,
+                mods,
+            })
+            .collect::<Vec<_>>()
+    };
 
-// Load settings from config file
-#[tauri::command]
-fn load_settings() -> Result<AppSettings, String> {
-    Ok(SETTINGS.read().unwrap().clone())
+    let report = ConflictReport {
+        game: game.key().to_owned(),
+        mods: load_order.mods().clone(),
+        movies: load_order.movies().clone(),
+        file_conflicts: to_conflicts(file_conflicts),
+        table_conflicts: to_conflicts(table_conflicts),
+    };
+
+    let data = match format {
+        "json" => serde_json::to_string_pretty(&report)
+            .map_err(|e| format!("Error exporting the conflict report: {}", e))?,
+        "text" => conflict_report_text(&report),
+        _ => return Err(format!("Unsupported conflict report format: {}.", format)),
+    };
+
+    std::fs::write(path, data).map_err(|e| format!("Error exporting the conflict report: {}", e))
 }
 
-// Save settings to config file
 #[tauri::command]
-fn save_settings(app_handle: tauri::AppHandle, settings: AppSettings) -> Result<(), String> {
+fn save_launch_preset(app: tauri::AppHandle, name: &str) -> Result<(), String> {
+    let name = name.trim();
+    if name.is_empty() {
+        return Err("Preset names cannot be empty.".to_string());
+    }
+
+    let game = GAME_SELECTED.read().unwrap().clone();
+    let mut settings = SETTINGS.read().unwrap().clone();
+    let options = settings.launch_options_for_game(&game);
     settings
-        .save(&app_handle)
+        .launch_presets
+        .entry(game.key().to_owned())
+        .or_default()
+        .insert(name.to_owned(), options);
+
+    settings
+        .save(&app)
         .map_err(|e| format!("Failed to save settings: {}", e))?;
     *SETTINGS.write().unwrap() = settings;
     Ok(())
 }
 
 #[tauri::command]
-fn get_available_languages() -> Vec<String> {
-    // Devuelve los idiomas disponibles en la aplicación
-    vec![
-        "English".to_string(),
-        "Español".to_string(),
-        "Français".to_string(),
-        "Deutsch".to_string(),
-        "Italiano".to_string(),
-    ]
+async fn apply_launch_preset(
+    app: tauri::AppHandle,
+    name: &str,
+) -> Result<Vec<LaunchOption>, String> {
+    let game = GAME_SELECTED.read().unwrap().clone();
+    let mut settings = SETTINGS.read().unwrap().clone();
+    let options = settings
+        .launch_presets
+        .get(game.key())
+        .and_then(|presets| presets.get(name))
+        .cloned()
+        .ok_or_else(|| format!("Launch preset \"{}\" not found.", name))?;
+
+    settings.set_launch_options_for_game(&game, &options);
+    settings
+        .save(&app)
+        .map_err(|e| format!("Failed to save settings: {}", e))?;
+
+    let game_path = settings
+        .game_path(&game)
+        .map_err(|e| format!("Error getting the game's path: {}", e))?;
+    *SETTINGS.write().unwrap() = settings;
+
+    LAUNCH_OPTIONS
+        .write()
+        .unwrap()
+        .generate_options(&app, &game, &game_path)
+        .map_err(|e| format!("Error generating launch options: {}", e))
 }
 
 #[tauri::command]
-fn get_available_date_formats() -> Vec<String> {
-    // Devuelve los formatos de fecha disponibles
-    vec![
-        "DD/MM/YYYY".to_string(),
-        "MM/DD/YYYY".to_string(),
-        "YYYY-MM-DD".to_string(),
-        "DD.MM.YYYY".to_string(),
-        "YYYY/MM/DD".to_string(),
-    ]
+fn list_launch_presets() -> Vec<String> {
+    let game = GAME_SELECTED.read().unwrap().clone();
+    SETTINGS
+        .read()
+        .unwrap()
+        .launch_presets
+        .get(game.key())
+        .map(|presets| presets.keys().cloned().collect())
+        .unwrap_or_default()
 }
 
 #[tauri::command]
-async fn browse_folder(
+async fn set_translation_language(
     app: tauri::AppHandle,
-    title: String,
-    current_path: String,
-) -> Option<String> {
-    use std::path::PathBuf;
-    use tauri_plugin_dialog::DialogExt;
+    lang: &str,
+) -> Result<Vec<LaunchOption>, String> {
+    let game = GAME_SELECTED.read().unwrap().clone();
+    let mut settings = SETTINGS.read().unwrap().clone();
+    let game_path = settings
+        .game_path(&game)
+        .map_err(|e| format!("Error getting the game's path: {}", e))?;
 
-    // Si se proporcionó una ruta válida, iniciar el diálogo en esa carpeta
-    let start_dir = PathBuf::from(&current_path);
+    let languages = LaunchOptions::available_languages(&game, &game_path);
+    if !languages.iter().any(|language| language == lang) {
+        return Err(format!(
+            "Language \"{}\" not found. Available languages: {}.",
+            lang,
+            languages.join(", ")
+        ));
+    }
 
-    // Mostrar el diálogo y obtener la carpeta seleccionada
-    let dialog = app
-        .dialog()
-        .file()
-        .set_directory(start_dir)
-        .set_title(title);
+    settings.launch_options.insert(
+        format!("{}:enable_translations", game.key()),
+        true.to_string(),
+    );
+    settings.launch_options.insert(
+        format!("{}:enable_translations:language", game.key()),
+        lang.to_owned(),
+    );
+    settings
+        .save(&app)
+        .map_err(|e| format!("Failed to save settings: {}", e))?;
+    *SETTINGS.write().unwrap() = settings;
 
-    dialog
-        .blocking_pick_folder()
-        .map(|path| path.as_path().unwrap().to_string_lossy().to_string())
+    LAUNCH_OPTIONS
+        .write()
+        .unwrap()
+        .generate_options(&app, &game, &game_path)
+        .map_err(|e| format!("Error generating launch options: {}", e))
 }
 
 #[tauri::command]
-async fn open_mod_folder(id: String) -> Result<(), String> {
-    let mod_id = unescape(&id);
+async fn get_rebalancer_candidates(app: tauri::AppHandle) -> Result<Vec<String>, String> {
+    let game = GAME_SELECTED.read().unwrap().clone();
+    let game_path = SETTINGS
+        .read()
+        .unwrap()
+        .game_path(&game)
+        .map_err(|e| format!("Error getting the game's path: {}", e))?;
+    let game_data_path = game
+        .data_path(&game_path)
+        .map_err(|e| format!("Error getting the game's data path: {}", e))?;
 
-    let game_config = GAME_CONFIG.lock().unwrap().clone().unwrap();
-    let mod_info = game_config.mods().get(&mod_id).unwrap();
-    match mod_info.paths().first().cloned() {
-        Some(mut path) => {
-            path.pop();
-            let _ = open::that(path);
-            Ok(())
-        }
-        None => Err("No path found".to_string()),
-    }
+    Ok(LAUNCH_OPTIONS
+        .write()
+        .unwrap()
+        .rebalancer_candidates(&app, &game, &game_data_path))
 }
 
 #[tauri::command]
-async fn open_mod_url(id: String) -> Result<(), String> {
-    let mod_id = unescape(&id);
-    if mod_id.is_empty() {
-        return Err("No mod ID found".to_string());
-    }
-
-    let game_config = GAME_CONFIG.lock().unwrap().clone().unwrap();
-    let mod_info = game_config.mods().get(&mod_id).unwrap();
-    let remote_id = mod_info.store_id();
+async fn get_active_sql_scripts(app: tauri::AppHandle) -> Result<Vec<ActiveSqlScript>, String> {
+    let game = GAME_SELECTED.read().unwrap().clone();
     let settings = SETTINGS.read().unwrap().clone();
+    let game_path = settings
+        .game_path(&game)
+        .map_err(|e| format!("Error getting the game's path: {}", e))?;
 
-    Integrations::open_remote_mod_url(&remote_id, settings.open_remote_mod_in_app)
-        .map_err(|e| format!("Error opening mod URL: {}", e))
+    // Regenerate the options so they reflect what would be used if we launch now. This is only a query, so don't touch the real ones.
+    let mut launch_options = LAUNCH_OPTIONS.read().unwrap().clone();
+    launch_options
+        .generate_options(&app, &game, &game_path)
+        .map_err(|e| format!("Error generating launch options: {}", e))?;
+    launch_options
+        .active_sql_scripts(&app, &game, &settings)
+        .map_err(|e| format!("Error getting the active sql scripts: {}", e))
 }
 
 #[tauri::command]
-async fn handle_change_game_selected(
-    app: tauri::AppHandle,
-    game_id: String,
-) -> Result<(Vec<TreeCategory>, Vec<ListItem>), String> {
-    let old_game = GAME_SELECTED.read().unwrap().clone();
-    let old_game_id = old_game.key();
-    change_game_selected(app, &game_id, old_game_id == game_id, false)
-        .await
-        .map_err(|e| format!("Error loading data: {}", e))
+fn get_script_parse_errors() -> Vec<ScriptParseError> {
+    LAUNCH_OPTIONS
+        .read()
+        .unwrap()
+        .script_parse_errors()
+        .to_vec()
 }
 
-async fn change_game_selected(
-    app: tauri::AppHandle,
-    game_id: &str,
-    reload_same_game: bool,
-    skip_network_update: bool,
-) -> Result<(Vec<TreeCategory>, Vec<ListItem>), String> {
-    let old_game = GAME_SELECTED.read().unwrap().clone();
-    let old_game_id = old_game.key();
-    use futures_util::TryFutureExt;
-    // If the game changed or we're initializing the program, change the game selected.
-    //
-    // This works because by default, the initially stored game selected is arena, and that one can never set manually.
-    if reload_same_game || game_id != old_game_id {
-        let result = load_data(&app, &game_id, skip_network_update);
-        result
-            .map_err(|e| format!("Error loading data: {}", e))
-            .await
-    } else {
-        Ok((vec![], vec![]))
-    }
+#[tauri::command]
+async fn preview_patcher_command(app: tauri::AppHandle) -> Result<Option<String>, String> {
+    let game = GAME_SELECTED.read().unwrap().clone();
+    let game_path = SETTINGS
+        .read()
+        .unwrap()
+        .game_path(&game)
+        .map_err(|e| format!("Error getting the game's path: {}", e))?;
+    let data_path = game
+        .data_path(&game_path)
+        .map_err(|e| format!("Error getting the game's data path: {}", e))?;
+
+    // Regenerate the options so they reflect what would be used if we launch now.
+    let mut launch_options = LAUNCH_OPTIONS.write().unwrap();
+    launch_options
+        .generate_options(&app, &game, &game_path)
+        .map_err(|e| format!("Error generating launch options: {}", e))?;
+    launch_options
+        .preview_patcher_command(&app, &game, &data_path)
+        .map_err(|e| format!("Error building the patcher command: {}", e))
 }
 
-async fn load_data(
-    app: &tauri::AppHandle,
-    game_id: &str,
-    skip_network_update: bool,
-) -> anyhow::Result<(Vec<TreeCategory>, Vec<ListItem>)> {
-    // We may receive invalid games here, so rule out the invalid ones.
-    let supported_games = SupportedGames::default();
-    match supported_games.game(game_id) {
-        Some(game) => {
-            // Schemas are optional, so don't interrupt loading due to they not being present.
-            //let schema_path = schemas_path().unwrap().join(game.schema_file_name());
-            //*SCHEMA.write().unwrap() = Schema::load(&schema_path, None).ok();
-            *GAME_SELECTED.write().unwrap() = game.clone();
+#[tauri::command]
+fn get_movie_pack_handling(app: tauri::AppHandle) -> Result<Vec<MoviePackHandling>, String> {
+    let game = GAME_SELECTED.read().unwrap().clone();
+    let game_path = SETTINGS
+        .read()
+        .unwrap()
+        .game_path(&game)
+        .map_err(|e| format!("Error getting the game's path: {}", e))?;
+    let data_path = game
+        .data_path(&game_path)
+        .map_err(|e| format!("Error getting the game's data path: {}", e))?;
+    let game_config = GAME_CONFIG
+        .lock()
+        .unwrap()
+        .clone()
+        .ok_or_else(|| "No game config loaded.".to_string())?;
+    let load_order = GAME_LOAD_ORDER.read().unwrap().clone();
 
-            // Trigger an update of all game configs, just in case one needs update.
-            let _ = GameConfig::update(game.key());
+    movie_pack_handling(&app, &game, &data_path, &game_config, &load_order)
+}
 
-            // Load the game's config and last known load order.
-            let mut load_order = LoadOrder::load(app, game).unwrap_or_else(|_| Default::default());
-            let mut game_config = GameConfig::load(app, game, true)?;
+#[tauri::command]
+fn get_masked_movies(app: tauri::AppHandle) -> Result<Vec<String>, String> {
+    let game = GAME_SELECTED.read().unwrap().clone();
+    if !game_uses_movie_masking(&game) {
+        return Ok(vec![]);
+    }
 
-            let settings = SETTINGS.read().unwrap().clone();
-            let game_path = settings.game_path(game)?;
+    let game_path = SETTINGS
+        .read()
+        .unwrap()
+        .game_path(&game)
+        .map_err(|e| format!("Error getting the game's path: {}", e))?;
+    let data_path = game
+        .data_path(&game_path)
+        .map_err(|e| format!("Error getting the game's data path: {}", e))?;
+    let game_config = GAME_CONFIG
+        .lock()
+        .unwrap()
+        .clone()
+        .ok_or_else(|| "No game config loaded.".to_string())?;
+    let load_order = GAME_LOAD_ORDER.read().unwrap().clone();
 
-            *GAME_LOAD_ORDER.write().unwrap() = load_order;
-            *GAME_CONFIG.lock().unwrap() = Some(game_config.clone());
+    // Same check as on launch: masks are only generated if the secondary folder is going to be loaded.
+    let mut folder_list = String::new();
+    let mut pack_list = String::new();
+    load_order
+        .build_load_order_string(
+            &app,
+            &game_config,
+            &game,
+            &data_path,
+            &mut pack_list,
+            &mut folder_list,
+        )
+        .map_err(|e| format!("Error building the load order: {}", e))?;
+
+    let secondary_mods_path =
+        secondary_mods_path(&app, game.key()).unwrap_or_else(|_| PathBuf::new());
+    if !secondary_mods_path.is_dir()
+        || !folder_list.contains(&load_order_path_string(&secondary_mods_path))
+    {
+        return Ok(vec![]);
+    }
 
-            // Trigger an update of all game profiles, just in case one needs update.
-            let _ = Profile::update(&game_config, game);
+    movies_to_mask(&game, &game_config, &data_path, &secondary_mods_path)
+        .map_err(|e| format!("Error reading the secondary mods path: {}", e))
+}
 
-            // Load the profile's list.
-            match Profile::profiles_for_game(app, game) {
-                Ok(profiles) => *GAME_PROFILES.write().unwrap() = profiles,
-                Err(error) => return Err(anyhow!("Error loading profiles: {}", error)),
-            }
-            /*
-                        self.actions_ui().profile_model().clear();
-                        for profile in self.game_profiles().read().unwrap().keys().sorted() {
-                            self.actions_ui().profile_combobox().add_item_q_string(&QString::from_std_str(profile));
-                        }
+#[tauri::command]
+fn get_mod_description(mod_id: &str) -> Result<String, String> {
+    let mod_id = unescape(mod_id);
+    let game_config = GAME_CONFIG.lock().unwrap();
+    let modd = game_config
+        .as_ref()
+        .ok_or_else(|| "No game config loaded.".to_string())?
+        .mods()
+        .get(&mod_id)
+        .ok_or_else(|| format!("Mod {} not found.", mod_id))?;
+
+    Ok(Integrations::mod_description(modd))
+}
 
-                        // Load the saves list for the selected game.
-                        let game_path_str = setting_string(game.key());
-                        let game_path = PathBuf::from(&game_path_str);
-                        if let Err(error) = self.load_saves_to_ui(game, &game_path) {
-                            show_dialog(self.main_window(), error, false);
-                        }
-            */
+#[tauri::command]
+fn get_mod_location_detail(
+    app: tauri::AppHandle,
+    mod_id: String,
+) -> Result<ModLocationDetail, String> {
+    let game = GAME_SELECTED.read().unwrap().clone();
+    let game_path = SETTINGS
+        .read()
+        .unwrap()
+        .game_path(&game)
+        .map_err(|e| format!("Error getting the game's path: {}", e))?;
+    let game_config = GAME_CONFIG
+        .lock()
+        .unwrap()
+        .clone()
+        .ok_or_else(|| "No game config loaded.".to_string())?;
+    let modd = game_config
+        .mods()
+        .get(&mod_id)
+        .ok_or_else(|| format!("Mod {} not found.", mod_id))?;
+
+    let data_path = path_to_absolute_string(&game.data_path(&game_path).unwrap_or_default());
+    let secondary_path =
+        path_to_absolute_string(&secondary_mods_path(&app, game.key()).unwrap_or_default());
+    let content_path = path_to_absolute_string(&game.content_path(&game_path).unwrap_or_default());
 
-            send_progress_event(&app, 10, 100);
+    let paths = mod_path_details(modd, &data_path, &secondary_path, &content_path);
 
-            let mut load_order = GAME_LOAD_ORDER.read().unwrap().clone();
-            let online_data_receiver = game_config
-                .update_mod_list(app, &game, &game_path, &mut load_order, skip_network_update)
-                .await?;
+    Ok(ModLocationDetail {
+        id: modd.id().to_owned(),
+        store_id: modd.store_id().clone(),
+        active_path: paths.first().map(|path| path.path.to_owned()),
+        paths,
+    })
+}
 
-            send_progress_event(&app, 30, 100);
+#[tauri::command]
+fn get_effective_override_rank(mod_id: &str) -> Result<OverrideRank, String> {
+    let mod_id = unescape(mod_id);
+    let game = GAME_SELECTED.read().unwrap().clone();
+    let game_path = SETTINGS
+        .read()
+        .unwrap()
+        .game_path(&game)
+        .map_err(|e| format!("Error getting the game's path: {}", e))?;
+    let load_order = GAME_LOAD_ORDER.read().unwrap().clone();
 
-            // NOTE: THIS CAN FAIL AND IT NEEDS TO NOT FAIL THE ENTIRE LOAD.
-            // TODO: Notify when this fails.
-            if let Some(tx_recv) = online_data_receiver {
-                let a = game_config
-                    .update_mod_list_with_online_data(tx_recv, app)
-                    .await;
-                dbg!(&a);
-            }
+    let vanilla_packs = game
+        .ca_packs_paths(&game_path)
+        .map_err(|e| format!("Error getting the vanilla packs: {}", e))?
+        .len();
 
-            send_progress_event(&app, 50, 100);
-            let mods = load_mods(&app, &game, &game_config).await?;
+    // Packs earlier in the list take priority. Movie packs override every mod, so they rank above them.
+    let is_movie = load_order.movies().contains(&mod_id);
+    let priority = load_order
+        .movies()
+        .iter()
+        .chain(load_order.mods().iter())
+        .collect::<Vec<_>>();
+
+    let position = priority.iter().position(|id| **id == mod_id);
+    let overridden_by = position
+        .map(|position| {
+            priority[..position]
+                .iter()
+                .map(|id| id.to_string())
+                .collect::<Vec<_>>()
+        })
+        .unwrap_or_default();
+
+    let summary = match position {
+        None => "Not loaded, so it doesn't override anything.".to_string(),
+        Some(_) if overridden_by.is_empty() => "Overrides vanilla and every other mod.".to_string(),
+        Some(_) => {
+            let movies = overridden_by
+                .iter()
+                .filter(|id| load_order.movies().contains(id))
+                .count();
+            let mods = overridden_by.len() - movies;
+            if is_movie {
+                format!(
+                    "Overrides vanilla and every regular mod, but may be overridden by {} movie packs loaded before it.",
+                    movies
+                )
+            } else if mods == 0 {
+                format!(
+                    "Overrides vanilla, but may be overridden by {} movie packs, as they override regular mods.",
+                    movies
+                )
+            } else {
+                format!(
+                    "Overrides vanilla, but may be overridden by {} mods loaded before it and {} movie packs, as they override regular mods.",
+                    mods, movies
+                )
+            }
+        }
+    };
 
-            send_progress_event(&app, 70, 100);
-            let items = load_packs(&app, &game_config, &game, &game_path, &load_order).await?;
+    Ok(OverrideRank {
+        id: mod_id,
+        is_movie,
+        position,
+        vanilla_packs,
+        overrides_vanilla: position.is_some(),
+        overridden_by,
+        summary,
+    })
+}
 
-            send_progress_event(&app, 90, 100);
-            *GAME_LOAD_ORDER.write().unwrap() = load_order;
-            *GAME_CONFIG.lock().unwrap() = Some(game_config.clone());
+#[tauri::command]
+fn find_mod_by_pack_name(pack_name: &str) -> Result<Option<FoundMod>, String> {
+    let game_config = GAME_CONFIG
+        .lock()
+        .unwrap()
+        .clone()
+        .ok_or_else(|| "No game config loaded.".to_string())?;
 
-            send_progress_event(&app, 100, 100);
+    let mut mods = game_config.mods().values().collect::<Vec<_>>();
+    mods.sort_by(|a, b| a.id().cmp(b.id()));
 
-            Ok((mods, items))
-        }
-        None => Err(anyhow!("Game {} is not a valid game.", game_id)),
+    // Ids take priority over file names, so a renamed file doesn't hide the mod with that id.
+    let found = mods
+        .iter()
+        .find(|modd| modd.id().eq_ignore_ascii_case(pack_name))
+        .or_else(|| {
+            mods.iter().find(|modd| {
+                modd.paths()
+                    .first()
+                    .and_then(|path| path.file_name())
+                    .is_some_and(|name| name.to_string_lossy().eq_ignore_ascii_case(pack_name))
+            })
+        });
+
+    Ok(found.map(|modd| FoundMod {
+        id: modd.id().to_owned(),
+        category: game_config.category_for_mod(modd.id()),
+    }))
+}
+
+/// Returns the mod tree with only the mods matching the query, case-insensitive. Categories with no matches are removed.
+///
+/// Fields can be name, creator, pack and description. If none are provided, all of them are checked.
+#[tauri::command]
+async fn filter_mods(
+    app: tauri::AppHandle,
+    query: &str,
+    fields: Vec<String>,
+    enabled_only: bool,
+) -> Result<Vec<TreeCategory>, String> {
+    let game_info = GAME_SELECTED.read().unwrap().clone();
+    let game_config = GAME_CONFIG
+        .lock()
+        .unwrap()
+        .clone()
+        .ok_or_else(|| "No game config loaded.".to_string())?;
+
+    let query = query.to_lowercase();
+    let check = |field: &str| fields.is_empty() || fields.iter().any(|name| name == field);
+    let matches = |modd: &Mod| {
+        query.is_empty()
+            || (check("name") && modd.name().to_lowercase().contains(&query))
+            || (check("creator") && modd.display_creator_name().to_lowercase().contains(&query))
+            || (check("pack")
+                && (modd.id().to_lowercase().contains(&query)
+                    || modd.paths().iter().any(|path| {
+                        path.file_name().is_some_and(|name| {
+                            name.to_string_lossy().to_lowercase().contains(&query)
+                        })
+                    })))
+            || (check("description") && modd.description().to_lowercase().contains(&query))
+    };
+
+    let mut categories = load_mods(&app, &game_info, &game_config)
+        .await
+        .map_err(|e| format!("Error loading data: {}", e))?;
+
+    for category in &mut categories {
+        category.children.retain(|item| {
+            (!enabled_only || item.is_checked)
+                && game_config
+                    .mods()
+                    .get(&unescape(&item.id))
+                    .is_some_and(matches)
+        });
     }
+
+    categories.retain(|category| !category.children.is_empty());
+    Ok(categories)
 }
 
-async fn load_mods(
-    app: &tauri::AppHandle,
-    game: &GameInfo,
-    game_config: &GameConfig,
-) -> anyhow::Result<Vec<TreeCategory>> {
-    use crate::mod_manager::secondary_mods_path;
-    use rpfm_lib::utils::path_to_absolute_string;
-    use std::time::UNIX_EPOCH;
+/// Returns the enabled mods with the exact same pack contents, grouped by hash.
+#[tauri::command]
+async fn find_duplicate_content() -> Result<Vec<DuplicatedContent>, String> {
+    use rayon::prelude::*;
 
-    let settings = SETTINGS.read().unwrap().clone();
-    let game_path = settings.game_path(game)?;
-    let game_last_update_date = last_game_update_date(game, &game_path)?;
-    let game_data_path = game.data_path(&game_path)?;
+    let game_config = GAME_CONFIG
+        .lock()
+        .unwrap()
+        .clone()
+        .ok_or_else(|| "No game config loaded.".to_string())?;
+    let load_order = GAME_LOAD_ORDER.read().unwrap().clone();
+
+    let paths = load_order
+        .mods()
+        .iter()
+        .chain(load_order.movies().iter())
+        .filter_map(|mod_id| {
+            let path = game_config.mods().get(mod_id)?.paths().first()?;
+            Some((mod_id.to_owned(), path.to_path_buf()))
+        })
+        .collect::<Vec<_>>();
+
+    // Hashing big packs takes a while, so do it in parallel and off the main thread.
+    let hashes = tauri::async_runtime::spawn_blocking(move || {
+        paths
+            .into_par_iter()
+            .filter_map(|(mod_id, path)| Some((mod_id, sha256::try_digest(path.as_path()).ok()?)))
+            .collect::<Vec<_>>()
+    })
+    .await
+    .map_err(|e| format!("Error hashing the mods: {}", e))?;
+
+    let mut groups: BTreeMap<String, Vec<String>> = BTreeMap::new();
+    for (mod_id, hash) in hashes {
+        groups.entry(hash).or_default().push(mod_id);
+    }
+
+    Ok(groups
+        .into_iter()
+        .filter(|(_, mods)| mods.len() > 1)
+        .map(|(hash, mut mods)| {
+            mods.sort();
+            DuplicatedContent { hash, mods }
+        })
+        .collect())
+}
+
+#[tauri::command]
+fn get_duplicated_mods(app: tauri::AppHandle) -> Result<Vec<DuplicatedMod>, String> {
+    let game = GAME_SELECTED.read().unwrap().clone();
+    let game_path = SETTINGS
+        .read()
+        .unwrap()
+        .game_path(&game)
+        .map_err(|e| format!("Error getting the game's path: {}", e))?;
+    let game_data_path = game
+        .data_path(&game_path)
+        .map_err(|e| format!("Error getting the game's data path: {}", e))?;
+    let game_config = GAME_CONFIG
+        .lock()
+        .unwrap()
+        .clone()
+        .ok_or_else(|| "No game config loaded.".to_string())?;
 
     let data_path = path_to_absolute_string(&game_data_path);
     let secondary_path =
-        path_to_absolute_string(&secondary_mods_path(app, game.key()).unwrap_or_default());
+        path_to_absolute_string(&secondary_mods_path(&app, game.key()).unwrap_or_default());
     let content_path = path_to_absolute_string(&game.content_path(&game_path).unwrap_or_default());
-    /*
-        // Initialize these here so they can be re-use.
-        let outdated_icon = icon_data("outdated.png").unwrap_or_else(|_| vec![]);
-        let outdated = tre("mod_outdated_description", &[&BASE64_STANDARD.encode(outdated_icon)]);
 
-        let data_older_than_secondary_icon = icon_data("data_older_than_secondary.png").unwrap_or_else(|_| vec![]);
-        let data_older_than_secondary = tre("mod_data_older_than_secondary", &[&BASE64_STANDARD.encode(data_older_than_secondary_icon)]);
+    let mut mods = game_config
+        .mods()
+        .values()
+        .filter(|modd| modd.paths().len() > 1)
+        .collect::<Vec<_>>();
+    mods.sort_by(|a, b| a.id().cmp(b.id()));
 
-        let data_older_than_content_icon = icon_data("data_older_than_content.png").unwrap_or_else(|_| vec![]);
-        let data_older_than_content = tre("mod_data_older_than_content", &[&BASE64_STANDARD.encode(data_older_than_content_icon)]);
+    Ok(mods
+        .iter()
+        .map(|modd| {
+            let (data_older_than_secondary, data_older_than_content, secondary_older_than_content) =
+                modd.priority_dating_flags(&data_path, &secondary_path, &content_path)
+                    .unwrap_or_default();
+
+            DuplicatedMod {
+                id: modd.id().to_owned(),
+                name: modd.name().to_owned(),
+                paths: mod_path_details(modd, &data_path, &secondary_path, &content_path),
+                data_older_than_secondary,
+                data_older_than_content,
+                secondary_older_than_content,
+            }
+        })
+        .collect())
+}
 
-        let secondary_older_than_content_icon = icon_data("secondary_older_than_content.png").unwrap_or_else(|_| vec![]);
-        let secondary_older_than_content = tre("mod_secondary_older_than_content", &[&BASE64_STANDARD.encode(secondary_older_than_content_icon)]);
-    */
-    // This loads mods per category, meaning all installed mod have to be in the categories list!!!!
-    let mut categories: Vec<TreeCategory> = vec![];
-    for category in game_config.categories_order() {
-        let mut cat_item = TreeCategory::default();
-        cat_item.id = "cat:".to_owned() + category;
-        cat_item.name = category.to_string();
+#[tauri::command]
+fn get_map_mods_status(app: tauri::AppHandle) -> Result<Vec<MapModStatus>, String> {
+    let game = GAME_SELECTED.read().unwrap().clone();
+    if game.key() != KEY_SHOGUN_2 {
+        return Ok(vec![]);
+    }
 
-        if let Some(mods) = game_config.categories().get(category) {
-            for mod_id in mods {
-                if let Some(modd) = game_config.mods().get(mod_id) {
-                    // Ignore registered mods with no path.
-                    if !modd.paths().is_empty() {
-                        let mut item = TreeItem::default();
-                        item.id = "mod:".to_owned() + mod_id;
-                        item.name = if modd.name() != modd.id() {
-                            if !modd.file_name().is_empty() {
-                                // Map filenames are folder names which we have to turn into packs.
-                                let pack_name = if let Some(alt_name) = modd.alt_name() {
-                                    alt_name.to_string()
-                                } else {
-                                    modd.file_name().split('/').last().unwrap().to_owned()
-                                };
+    let game_path = SETTINGS
+        .read()
+        .unwrap()
+        .game_path(&game)
+        .map_err(|e| format!("Error getting the game's path: {}", e))?;
+    let game_data_path = game
+        .data_path(&game_path)
+        .map_err(|e| format!("Error getting the game's data path: {}", e))?;
+    let game_config = GAME_CONFIG
+        .lock()
+        .unwrap()
+        .clone()
+        .ok_or_else(|| "No game config loaded.".to_string())?;
 
-                                format!(
-                                    "<b>{}</b> <i>({} - {})</i>",
-                                    modd.name(),
-                                    pack_name,
-                                    modd.id()
-                                )
-                            } else {
-                                format!("<b>{}</b> <i>({})</i>", modd.name(), modd.id())
-                            }
-                        } else {
-                            format!("<i>{}</i>", modd.name())
-                        };
+    let data_path = path_to_absolute_string(&game_data_path);
+    let secondary_path =
+        path_to_absolute_string(&secondary_mods_path(&app, game.key()).unwrap_or_default());
+    let content_path = path_to_absolute_string(&game.content_path(&game_path).unwrap_or_default());
 
-                        item.creator = modd.creator_name().to_owned();
-                        item.r#type = modd.pack_type().to_string();
-                        item.description = modd.description().to_owned();
+    let mut mods = game_config
+        .mods()
+        .values()
+        .filter(|modd| modd.is_map_mod())
+        .collect::<Vec<_>>();
+    mods.sort_by(|a, b| a.id().cmp(b.id()));
 
-                        // TODO: show discrepancies between steam's reported data and real data.
-                        item.size = if *modd.file_size() != 0 {
-                            format!("{:.2} MB", *modd.file_size() as f64 / 1024.0 / 1024.0)
-                        } else {
-                            let size = modd.paths()[0].metadata()?.len();
-                            format!("{:.2} MB", size as f64 / 1024.0 / 1024.0)
-                        };
+    Ok(mods
+        .iter()
+        .filter_map(|modd| {
+            let pack_name = modd.alt_name()?;
+            let map_name = pack_name.strip_suffix(".pack")?.to_owned();
+            let pack_path = modd.paths().iter().find(|path| {
+                path.file_name()
+                    .is_some_and(|name| name == pack_name.as_str())
+            });
 
-                        item.created = if *modd.time_created() != 0 {
-                            *modd.time_created() as u64
-                        } else if cfg!(target_os = "windows") {
-                            let date = modd.paths()[0]
-                                .metadata()?
-                                .created()?
-                                .duration_since(UNIX_EPOCH)?;
-                            date.as_secs() as u64
-                        } else {
-                            0
-                        };
+            // Only the tables generated from the map info make the map show up in game, so check they're there.
+            let pack = pack_path.and_then(|path| {
+                Pack::read_and_merge(&[path.to_path_buf()], true, false, false, false).ok()
+            });
+            let battles_path = format!("db/battles_tables/{}", map_name);
+            let loc_path = format!("text/db/{}.loc", map_name);
+            let has_file = |file_path: &str| {
+                pack.as_ref().is_some_and(|pack| {
+                    pack.files()
+                        .keys()
+                        .any(|path| path.eq_ignore_ascii_case(file_path))
+                })
+            };
 
-                        item.updated = *modd.time_updated() as u64;
-                        /*
-                                                let mut flags_description = String::new();
-                                                if modd.outdated(game_last_update_date) {
-                                                    item_flags.set_data_2a(&QVariant::from_bool(true), FLAG_MOD_IS_OUTDATED);
-                                                    flags_description.push_str(&outdated);
-                                                }
-
-                                                if let Ok(flags) = modd.priority_dating_flags(&data_path, &secondary_path, &content_path) {
-                                                    item_flags.set_data_2a(&QVariant::from_bool(flags.0), FLAG_MOD_DATA_IS_OLDER_THAN_SECONDARY);
-                                                    item_flags.set_data_2a(&QVariant::from_bool(flags.1), FLAG_MOD_DATA_IS_OLDER_THAN_CONTENT);
-                                                    item_flags.set_data_2a(&QVariant::from_bool(flags.2), FLAG_MOD_SECONDARY_IS_OLDER_THAN_CONTENT);
-
-                                                    if flags.0 {
-                                                        flags_description.push_str(&data_older_than_secondary);
-                                                    }
-
-                                                    if flags.1 {
-                                                        flags_description.push_str(&data_older_than_content);
-                                                    }
-
-                                                    if flags.2 {
-                                                        flags_description.push_str(&secondary_older_than_content);
-                                                    }
-                                                }
-
-                                                if !flags_description.is_empty() {
-                                                    flags_description = tr("mod_flags_description") + "<ul>" + &flags_description + "<ul/>";
-                                                    item_flags.set_tool_tip(&QString::from_std_str(&flags_description));
-                                                }
-                        */
-                        let (l_data, l_secondary, l_content) =
-                            modd.location(&data_path, &secondary_path, &content_path);
-                        let mut locations = vec![];
+            Some(MapModStatus {
+                id: modd.id().to_owned(),
+                name: modd.name().to_owned(),
+                map_name,
+                pack_name: pack_name.to_owned(),
+                converted: pack_path.is_some(),
+                pack_path: pack_path.map(|path| path_to_absolute_string(path)),
+                location: pack_path.map(|path| {
+                    Mod::path_location(path, &data_path, &secondary_path, &content_path)
+                }),
+                has_battles_table: has_file(&battles_path),
+                has_loc_table: has_file(&loc_path),
+            })
+        })
+        .collect())
+}
 
-                        if l_data {
-                            locations.push("Data".to_owned());
-                        }
-                        if l_secondary {
-                            locations.push("Secondary".to_owned());
-                        }
+#[tauri::command]
+async fn set_preferred_mod_location(
+    app: tauri::AppHandle,
+    mod_id: String,
+    location: Option<ModLocation>,
+) -> Result<Vec<ListItem>, String> {
+    check_view_only()?;
 
-                        match l_content {
-                            StoreId::None => {},
-                            StoreId::Steam(id) |
-                            StoreId::Epic(id) |
-                            StoreId::Nexus(id) |
-                            StoreId::ModDB(id) |
-                            StoreId::LoversLab(id) |
-                            StoreId::Github(id) => {
-                                locations.push(format!("Content ({})", id));
-                            }
-                        }
+    let game_info = GAME_SELECTED.read().unwrap().clone();
+    let game_path = SETTINGS
+        .read()
+        .unwrap()
+        .game_path(&game_info)
+        .map_err(|e| format!("Error getting the game's path: {}", e))?;
+    let game_data_path = game_info
+        .data_path(&game_path)
+        .map_err(|e| format!("Error getting the game's data path: {}", e))?;
+    let mut game_config = GAME_CONFIG
+        .lock()
+        .unwrap()
+        .clone()
+        .ok_or_else(|| "No game config loaded.".to_string())?;
+    let mut load_order = GAME_LOAD_ORDER.read().unwrap().clone();
 
-                        item.location = locations.join(",");
+    let data_path = path_to_absolute_string(&game_data_path);
+    let secondary_path =
+        path_to_absolute_string(&secondary_mods_path(&app, game_info.key()).unwrap_or_default());
+    let content_path =
+        path_to_absolute_string(&game_info.content_path(&game_path).unwrap_or_default());
+
+    let modd = game_config
+        .mods_mut()
+        .get_mut(&mod_id)
+        .ok_or_else(|| format!("Mod {} not found.", mod_id))?;
+
+    // None clears the preference, so the mod goes back to the default location.
+    if let Some(location) = location {
+        if !modd.paths().iter().any(|path| {
+            Mod::path_location(path, &data_path, &secondary_path, &content_path) == location
+        }) {
+            return Err(format!(
+                "Mod {} has no copy in the {:?} location.",
+                mod_id, location
+            ));
+        }
+    }
+
+    modd.set_preferred_location(location);
+
+    // update_mod_list reorders the paths using the preferred location.
+    let _ = game_config
+        .update_mod_list(&app, &game_info, &game_path, &mut load_order, false)
+        .await
+        .map_err(|e| format!("Error loading data: {}", e))?;
+    let items = load_packs(&app, &game_config, &game_info, &game_path, &load_order)
+        .await
+        .map_err(|e| format!("Error loading data: {}", e))?;
+
+    game_config
+        .save_or_defer(&app, &game_info)
+        .map_err(|e| format!("Error saving data: {}", e))?;
+
+    *GAME_LOAD_ORDER.write().unwrap() = load_order;
+    *GAME_CONFIG.lock().unwrap() = Some(game_config);
+
+    Ok(items)
+}
+
+#[tauri::command]
+fn prepare_game_folders() -> Result<(), String> {
+    let game = GAME_SELECTED.read().unwrap().clone();
+    let game_path = SETTINGS
+        .read()
+        .unwrap()
+        .game_path(&game)
+        .map_err(|e| format!("Error getting the game's path: {}", e))?;
+
+    ensure_game_folders(&game, &game_path)
+        .map_err(|e| format!("Error preparing the game's folders: {}", e))
+}
+
+#[tauri::command]
+fn check_secondary_folder_placement(
+    app: tauri::AppHandle,
+) -> Result<SecondaryFolderPlacement, String> {
+    let game = GAME_SELECTED.read().unwrap().clone();
+    let game_path = SETTINGS
+        .read()
+        .unwrap()
+        .game_path(&game)
+        .map_err(|e| format!("Error getting the game's path: {}", e))?;
+    let data_path = game
+        .data_path(&game_path)
+        .map_err(|e| format!("Error getting the game's data path: {}", e))?;
+    let secondary_path = secondary_mods_path(&app, game.key())
+        .map_err(|e| format!("Error getting the secondary mods path: {}", e))?;
+
+    let same_volume = is_same_volume(&secondary_path, &data_path)
+        .map_err(|e| format!("Error checking the volumes of the folders: {}", e))?;
+
+    Ok(SecondaryFolderPlacement {
+        secondary_path: path_to_absolute_string(&secondary_path),
+        data_path: path_to_absolute_string(&data_path),
+        same_volume,
+    })
+}
+
+#[tauri::command]
+fn supports_secondary_folder(game_id: &str) -> Result<bool, String> {
+    SupportedGames::default()
+        .game(game_id)
+        .map(game_supports_secondary_folder)
+        .ok_or_else(|| format!("Game {} not supported.", game_id))
+}
+
+#[tauri::command]
+fn get_game_capabilities(game_id: &str) -> Result<GameCapabilities, String> {
+    let games = SupportedGames::default();
+    let game = games
+        .game(game_id)
+        .ok_or_else(|| format!("Game {} not supported.", game_id))?;
+
+    Ok(GameCapabilities {
+        raw_db_version: *game.raw_db_version(),
+        supports_secondary_folder: game_supports_secondary_folder(game),
+        supports_content_loading: game_supports_content_loading(game),
+        supports_custom_mod_list: game_supports_custom_mod_list(game),
+        supports_exclude_pack_file: !game_uses_movie_masking(game),
+        uses_movie_masking: game_uses_movie_masking(game),
+        supports_extra_launch_args: game_supports_extra_launch_args(game),
+    })
+}
+
+#[tauri::command]
+fn supported_launch_options(game_id: &str) -> Result<Vec<String>, String> {
+    let games = SupportedGames::default();
+    let game = games
+        .game(game_id)
+        .ok_or_else(|| format!("Game {} not supported.", game_id))?;
+
+    Ok(supported_options(game.key())
+        .iter()
+        .map(|option| option.to_string())
+        .collect())
+}
+
+#[tauri::command]
+fn get_sidebar_icons() -> Vec<SidebarIcon> {
+    let games = SupportedGames::default();
+    let games = games.games_sorted();
+    let mut icons = Vec::with_capacity(games.len() - 1);
+    for game in games {
+        if game.key() != KEY_ARENA {
+            icons.push(SidebarIcon {
+                id: game.key().to_string(),
+                name: game.display_name().to_string(),
+                icon: game.icon_small().to_string(),
+            });
+        }
+    }
+
+    icons
+}
+
+/// Returns the load order entries that only match their file on disk if we ignore case.
+///
+/// These fail to load on case-sensitive filesystems.
+#[tauri::command]
+fn check_case_mismatches() -> Result<Vec<CaseMismatch>, String> {
+    let game_config = GAME_CONFIG
+        .lock()
+        .unwrap()
+        .clone()
+        .ok_or_else(|| "No game config loaded.".to_string())?;
+    let load_order = GAME_LOAD_ORDER.read().unwrap().clone();
+
+    // The load order may keep an id with a different case than the mod, so look them up ignoring it.
+    let mods_by_lowercase_id = game_config
+        .mods()
+        .iter()
+        .map(|(id, modd)| (id.to_lowercase(), modd))
+        .collect::<HashMap<_, _>>();
+
+    let mut mismatches = vec![];
+    for mod_id in load_order.mods().iter().chain(load_order.movies().iter()) {
+        let Some(path) = game_config
+            .mods()
+            .get(mod_id)
+            .or_else(|| mods_by_lowercase_id.get(&mod_id.to_lowercase()).copied())
+            .and_then(|modd| modd.paths().first())
+        else {
+            continue;
+        };
+
+        // Check the real name in the folder, as some filesystems will happily open the file with the wrong case.
+        let file_names = path
+            .parent()
+            .and_then(|parent| std::fs::read_dir(parent).ok())
+            .map(|entries| {
+                entries
+                    .flatten()
+                    .map(|entry| entry.file_name().to_string_lossy().to_string())
+                    .filter(|name| name.eq_ignore_ascii_case(mod_id))
+                    .collect::<Vec<_>>()
+            })
+            .unwrap_or_default();
+
+        if let Some(file_name) = file_names.first().cloned() {
+            if !file_names.contains(mod_id) {
+                mismatches.push(CaseMismatch {
+                    id: mod_id.to_owned(),
+                    path: path
+                        .with_file_name(&file_name)
+                        .to_string_lossy()
+                        .to_string(),
+                    file_name,
+                });
+            }
+        }
+    }
+
+    Ok(mismatches)
+}
+
+/// Returns a hash of the enabled mods, in load order, so users can easily check if they have the same setup.
+#[tauri::command]
+fn load_order_hash() -> Result<String, String> {
+    let game_config = GAME_CONFIG
+        .lock()
+        .unwrap()
+        .clone()
+        .ok_or_else(|| "No game config loaded.".to_string())?;
+    let load_order = GAME_LOAD_ORDER.read().unwrap().clone();
+
+    let mut hashes = String::new();
+    for mod_id in load_order.mods().iter().chain(load_order.movies().iter()) {
+        let path = game_config
+            .mods()
+            .get(mod_id)
+            .and_then(|modd| modd.paths().first())
+            .ok_or_else(|| format!("Mod {} is not installed.", mod_id))?;
+
+        let hash = sha256::try_digest(path.as_path())
+            .map_err(|e| format!("Error hashing mod {}: {}", mod_id, e))?;
+        hashes.push_str(&hash);
+    }
+
+    Ok(sha256::digest(hashes))
+}
+
+#[tauri::command]
+fn export_state_json() -> Result<String, String> {
+    let settings = SETTINGS.read().unwrap().clone();
+    let game = GAME_SELECTED.read().unwrap().clone();
+    let game_config = GAME_CONFIG.lock().unwrap().clone().unwrap_or_default();
+    let load_order = GAME_LOAD_ORDER.read().unwrap().clone();
+    let data_path = settings
+        .game_path(&game)
+        .ok()
+        .and_then(|game_path| game.data_path(&game_path).ok())
+        .unwrap_or_default();
+
+    let games = SupportedGames::default()
+        .games_sorted()
+        .iter()
+        .filter(|game| game.key() != KEY_ARENA)
+        .map(|game| ExportedGame {
+            key: game.key().to_string(),
+            name: game.display_name().to_string(),
+            path: settings
+                .game_path(game)
+                .map(|path| path.to_string_lossy().to_string())
+                .unwrap_or_default(),
+        })
+        .collect();
+
+    let mut mods = game_config
+        .mods()
+        .values()
+        .filter(|modd| !modd.paths().is_empty())
+        .map(|modd| ExportedMod {
+            id: modd.id().to_owned(),
+            name: modd.name().to_owned(),
+            store_id: modd.store_id().clone(),
+            enabled: modd.enabled(&game, &data_path),
+            r#type: modd.pack_type().to_string(),
+            paths: modd
+                .paths()
+                .iter()
+                .map(|path| path.to_string_lossy().to_string())
+                .collect(),
+        })
+        .collect::<Vec<_>>();
+    mods.sort_by(|a, b| a.id.cmp(&b.id));
+
+    let state = StateExport {
+        version: STATE_EXPORT_VERSION,
+        games,
+        selected_game: game.key().to_string(),
+        categories: game_config.categories().clone(),
+        categories_order: game_config.categories_order().clone(),
+        mods,
+        load_order: ExportedLoadOrder {
+            automatic: *load_order.automatic(),
+            mods: load_order.mods().clone(),
+            movies: load_order.movies().clone(),
+        },
+    };
+
+    serde_json::to_string_pretty(&state).map_err(|e| format!("Error exporting state: {}", e))
+}
+
+/// Returns the published file id of a workshop url. Supports urls with the id as query param, urls with it as last path segment, and bare ids.
+#[tauri::command]
+fn parse_workshop_url(url: &str) -> Result<String, String> {
+    let url = url.trim();
+    let is_id = |id: &str| !id.is_empty() && id.chars().all(|char| char.is_ascii_digit());
+    if is_id(url) {
+        return Ok(url.to_owned());
+    }
+
+    let parsed = url::Url::parse(url).map_err(|e| format!("Invalid URL: {}", e))?;
+    let id = parsed
+        .query_pairs()
+        .find(|(key, _)| key == "id")
+        .map(|(_, id)| id.to_string())
+        .or_else(|| {
+            parsed
+                .path_segments()?
+                .rev()
+                .find(|segment| !segment.is_empty())
+                .map(|segment| segment.to_owned())
+        })
+        .ok_or_else(|| format!("No workshop id found in {}.", url))?;
+
+    if is_id(&id) {
+        Ok(id)
+    } else {
+        Err(format!("Invalid workshop id: {}.", id))
+    }
+}
+
+#[tauri::command]
+async fn import_load_order_from_url(url: String) -> Result<ImportedLoadOrderReport, String> {
+    let url = url::Url::parse(&url).map_err(|e| format!("Invalid URL: {}", e))?;
+    if url.scheme() != "https" {
+        return Err("Only https links are supported.".to_string());
+    }
+
+    let mut response = reqwest::get(url.as_str())
+        .await
+        .and_then(|response| response.error_for_status())
+        .map_err(|e| format!("Error downloading the load order: {}", e))?;
+
+    let too_big = format!(
+        "The load order is bigger than the max allowed size ({} bytes).",
+        IMPORTED_LOAD_ORDER_MAX_SIZE
+    );
+    if response
+        .content_length()
+        .is_some_and(|length| length > IMPORTED_LOAD_ORDER_MAX_SIZE)
+    {
+        return Err(too_big);
+    }
+
+    // The content length may be missing or lie, so check the size as we download.
+    let mut data = vec![];
+    while let Some(chunk) = response
+        .chunk()
+        .await
+        .map_err(|e| format!("Error downloading the load order: {}", e))?
+    {
+        data.extend_from_slice(&chunk);
+        if data.len() as u64 > IMPORTED_LOAD_ORDER_MAX_SIZE {
+            return Err(too_big);
+        }
+    }
+
+    let data = String::from_utf8(data)
+        .map_err(|e| format!("The downloaded load order is not valid text: {}", e))?;
+
+    imported_load_order_report(&ImportedLoadOrderMode::detect(data))
+        .map_err(|e| format!("Error parsing the load order: {}", e))
+}
+
+/// Checks a load order file against the installed mods. Mode can be runcher, modlist, or empty to detect it from the file.
+#[tauri::command]
+fn import_load_order(path: &str, mode: &str) -> Result<ImportedLoadOrderReport, String> {
+    let data = std::fs::read_to_string(path)
+        .map_err(|e| format!("Error reading the load order: {}", e))?;
+
+    let mode = match mode {
+        "runcher" => ImportedLoadOrderMode::Runcher(data),
+        "modlist" => ImportedLoadOrderMode::Modlist(data),
+        "" => ImportedLoadOrderMode::detect(data),
+        _ => return Err(format!("Unsupported load order mode: {}.", mode)),
+    };
+
+    imported_load_order_report(&mode).map_err(|e| format!("Error parsing the load order: {}", e))
+}
+
+#[tauri::command]
+fn export_load_order(path: &str) -> Result<(), String> {
+    let game = GAME_SELECTED.read().unwrap().clone();
+    let game_config = GAME_CONFIG
+        .lock()
+        .unwrap()
+        .clone()
+        .ok_or_else(|| "No game config loaded.".to_string())?;
+    let load_order = GAME_LOAD_ORDER.read().unwrap().clone();
+
+    ShareableLoadOrder::new(&game, &load_order, &game_config)
+        .save(&PathBuf::from(path))
+        .map_err(|e| format!("Error exporting the load order: {}", e))
+}
+
+/// Locks or unlocks every game that can be locked. Returns the result for each of them.
+#[tauri::command]
+fn lock_all_games(lock: bool) -> Result<Vec<GameLockResult>, String> {
+    check_view_only()?;
+
+    let settings = SETTINGS.read().unwrap().clone();
+
+    Ok(SupportedGames::default()
+        .games_sorted()
+        .iter()
+        .filter(|game| game.key() != KEY_ARENA)
+        .filter_map(|game| {
+            let game_path = settings.game_path(game).ok().filter(|path| path.is_dir())?;
+            let (can_be_locked, _) = Integrations::game_locked_state(game, &game_path);
+            if !can_be_locked {
+                return None;
+            }
+
+            let (locked, error) = match Integrations::set_game_locked(game, &game_path, lock) {
+                Ok(locked) => (locked, None),
+                Err(error) => (
+                    Integrations::game_locked_state(game, &game_path).1,
+                    Some(error.to_string()),
+                ),
+            };
+
+            Some(GameLockResult {
+                key: game.key().to_string(),
+                name: game.display_name().to_string(),
+                locked,
+                error,
+            })
+        })
+        .collect())
+}
+
+/// Returns the lock details of the selected game's appmanifest.
+#[tauri::command]
+fn get_lock_details() -> Result<GameLockDetails, String> {
+    let game = GAME_SELECTED.read().unwrap().clone();
+    let game_path = SETTINGS
+        .read()
+        .unwrap()
+        .game_path(&game)
+        .map_err(|e| format!("Error getting the game's path: {}", e))?;
+
+    Integrations::game_lock_details(&game, &game_path)
+        .map_err(|e| format!("Error getting the lock details: {}", e))
+}
+
+/// Returns the selected game and its path, failing if the game has no manifest to lock.
+fn lockable_selected_game() -> Result<(GameInfo, PathBuf), String> {
+    let game = GAME_SELECTED.read().unwrap().clone();
+    let game_path = SETTINGS
+        .read()
+        .unwrap()
+        .game_path(&game)
+        .map_err(|e| format!("Error getting the game's path: {}", e))?;
+
+    let details = Integrations::game_lock_details(&game, &game_path)
+        .map_err(|e| format!("Error getting the lock details: {}", e))?;
+    if !details.exists {
+        return Err(format!(
+            "The game's manifest was not found at {}. Only games installed through Steam can be locked.",
+            details.path
+        ));
+    }
+
+    Ok((game, game_path))
+}
+
+#[tauri::command]
+fn can_lock_game() -> Result<bool, String> {
+    let game = GAME_SELECTED.read().unwrap().clone();
+    let game_path = SETTINGS
+        .read()
+        .unwrap()
+        .game_path(&game)
+        .map_err(|e| format!("Error getting the game's path: {}", e))?;
+
+    Ok(Integrations::game_locked_state(&game, &game_path).0)
+}
+
+#[tauri::command]
+fn is_game_locked() -> Result<bool, String> {
+    let (game, game_path) = lockable_selected_game()?;
+    Ok(Integrations::game_locked_state(&game, &game_path).1)
+}
+
+/// Locks or unlocks the selected game, so Steam cannot update it. Returns if the game is locked after the change.
+#[tauri::command]
+fn toggle_game_locked(lock: bool) -> Result<bool, String> {
+    check_view_only()?;
+
+    let (game, game_path) = lockable_selected_game()?;
+    Integrations::set_game_locked(&game, &game_path, lock)
+        .map_err(|e| format!("Error changing the game's lock: {}", e))
+}
+
+/// Returns a summary of every game with a path configured. Games other than the selected one are read from their saved config.
+#[tauri::command]
+fn get_games_overview(app: tauri::AppHandle) -> Result<Vec<GameOverview>, String> {
+    let settings = SETTINGS.read().unwrap().clone();
+    let selected_game = GAME_SELECTED.read().unwrap().clone();
+
+    Ok(SupportedGames::default()
+        .games_sorted()
+        .iter()
+        .filter(|game| game.key() != KEY_ARENA)
+        .filter_map(|game| {
+            let game_path = settings.game_path(game).ok().filter(|path| path.is_dir())?;
+            let data_path = game.data_path(&game_path).unwrap_or_default();
+            let selected = game.key() == selected_game.key();
+
+            let game_config = if selected {
+                GAME_CONFIG.lock().unwrap().clone()
+            } else {
+                GameConfig::load(&app, game, false).ok()
+            }
+            .unwrap_or_default();
+
+            let installed = game_config
+                .mods()
+                .values()
+                .filter(|modd| !modd.paths().is_empty())
+                .collect::<Vec<_>>();
+            let enabled_mods = installed
+                .iter()
+                .filter(|modd| modd.enabled(game, &data_path))
+                .count();
+            let (can_be_locked, locked) = Integrations::game_locked_state(game, &game_path);
+
+            Some(GameOverview {
+                key: game.key().to_string(),
+                name: game.display_name().to_string(),
+                path: game_path.to_string_lossy().to_string(),
+                selected,
+                installed_mods: installed.len(),
+                enabled_mods,
+                can_be_locked,
+                locked,
+            })
+        })
+        .collect())
+}
+
+#[tauri::command]
+async fn handle_mod_toggled(
+    app: tauri::AppHandle,
+    mod_id: &str,
+    is_checked: bool,
+) -> Result<Vec<ListItem>, String> {
+    check_view_only()?;
+
+    let mod_id = unescape(mod_id);
+
+    println!("Mod {} checkbox changed to: {}", mod_id, is_checked);
+
+    let game_info = GAME_SELECTED.read().unwrap().clone();
+    let game_path = SETTINGS.read().unwrap().game_path(&game_info).unwrap();
+    let mut game_config = GAME_CONFIG.lock().unwrap().clone().unwrap();
+    let mut load_order = GAME_LOAD_ORDER.read().unwrap().clone();
+
+    game_config
+        .mods_mut()
+        .get_mut(&mod_id)
+        .unwrap()
+        .set_enabled(is_checked);
+
+    let _ = game_config
+        .update_mod_list(&app, &game_info, &game_path, &mut load_order, false)
+        .await
+        .map_err(|e| format!("Error loading data: {}", e))?;
+    let items = load_packs(&app, &game_config, &game_info, &game_path, &load_order)
+        .await
+        .map_err(|e| format!("Error loading data: {}", e))?;
+
+    game_config
+        .save_or_defer(&app, &game_info)
+        .map_err(|e| format!("Error saving data: {}", e))?;
+
+    *GAME_LOAD_ORDER.write().unwrap() = load_order;
+    *GAME_CONFIG.lock().unwrap() = Some(game_config);
+
+    Ok(items)
+}
+
+/// Enables or disables every mod in a category. Returns the new pack list, and the mods that couldn't be toggled.
+#[tauri::command]
+async fn handle_category_toggled(
+    app: tauri::AppHandle,
+    category_id: &str,
+    is_checked: bool,
+) -> Result<(Vec<ListItem>, Vec<String>), String> {
+    check_view_only()?;
+
+    let category_id = unescape(category_id);
+
+    let game_info = GAME_SELECTED.read().unwrap().clone();
+    let game_path = SETTINGS
+        .read()
+        .unwrap()
+        .game_path(&game_info)
+        .map_err(|e| format!("Error getting the game's path: {}", e))?;
+    let game_data_path = game_info
+        .data_path(&game_path)
+        .map_err(|e| format!("Error getting the game's data path: {}", e))?;
+    let mut game_config = GAME_CONFIG
+        .lock()
+        .unwrap()
+        .clone()
+        .ok_or_else(|| "No game config loaded.".to_string())?;
+    let mut load_order = GAME_LOAD_ORDER.read().unwrap().clone();
+
+    let mod_ids = game_config
+        .categories()
+        .get(&category_id)
+        .cloned()
+        .ok_or_else(|| format!("Category {} not found.", category_id))?;
+
+    let mut skipped = vec![];
+    for mod_id in &mod_ids {
+        if let Some(modd) = game_config.mods_mut().get_mut(mod_id) {
+            if modd.can_be_toggled(&game_info, &game_data_path) {
+                modd.set_enabled(is_checked);
+            } else {
+                skipped.push(mod_id.to_owned());
+            }
+        }
+    }
+
+    game_config
+        .update_mod_list(&app, &game_info, &game_path, &mut load_order, false)
+        .await
+        .map_err(|e| format!("Error loading data: {}", e))?;
+    let items = load_packs(&app, &game_config, &game_info, &game_path, &load_order)
+        .await
+        .map_err(|e| format!("Error loading data: {}", e))?;
+
+    game_config
+        .save_or_defer(&app, &game_info)
+        .map_err(|e| format!("Error saving data: {}", e))?;
+
+    *GAME_LOAD_ORDER.write().unwrap() = load_order;
+    *GAME_CONFIG.lock().unwrap() = Some(game_config);
+
+    Ok((items, skipped))
+}
+
+/// Same as [handle_mod_toggled], but also reports the packs whose order or movie handling changed due to the toggle.
+#[tauri::command]
+async fn handle_mod_toggled_with_diff(
+    app: tauri::AppHandle,
+    mod_id: &str,
+    is_checked: bool,
+) -> Result<ModToggleDiff, String> {
+    check_view_only()?;
+
+    let game_info = GAME_SELECTED.read().unwrap().clone();
+    let game_path = SETTINGS
+        .read()
+        .unwrap()
+        .game_path(&game_info)
+        .map_err(|e| format!("Error getting the game's path: {}", e))?;
+    let game_data_path = game_info
+        .data_path(&game_path)
+        .map_err(|e| format!("Error getting the game's data path: {}", e))?;
+    let game_config = GAME_CONFIG
+        .lock()
+        .unwrap()
+        .clone()
+        .ok_or_else(|| "No game config loaded.".to_string())?;
+    let load_order = GAME_LOAD_ORDER.read().unwrap().clone();
+
+    let old_items = load_packs(&app, &game_config, &game_info, &game_path, &load_order)
+        .await
+        .map_err(|e| format!("Error loading data: {}", e))?;
+    let old_movies =
+        movie_pack_handling(&app, &game_info, &game_data_path, &game_config, &load_order)?;
+
+    let items = handle_mod_toggled(app.clone(), mod_id, is_checked).await?;
+
+    let game_config = GAME_CONFIG
+        .lock()
+        .unwrap()
+        .clone()
+        .ok_or_else(|| "No game config loaded.".to_string())?;
+    let load_order = GAME_LOAD_ORDER.read().unwrap().clone();
+    let new_movies =
+        movie_pack_handling(&app, &game_info, &game_data_path, &game_config, &load_order)?;
+
+    let old_orders = old_items
+        .iter()
+        .map(|item| (item.id.to_owned(), item.order))
+        .collect::<BTreeMap<_, _>>();
+    let new_orders = items
+        .iter()
+        .map(|item| (item.id.to_owned(), item.order))
+        .collect::<BTreeMap<_, _>>();
+
+    let mut ids = old_orders
+        .keys()
+        .chain(new_orders.keys())
+        .collect::<Vec<_>>();
+    ids.sort();
+    ids.dedup();
+
+    let order_changes = ids
+        .into_iter()
+        .filter_map(|id| {
+            let old_order = old_orders.get(id).copied();
+            let new_order = new_orders.get(id).copied();
+            if old_order != new_order {
+                Some(PackOrderChange {
+                    id: id.to_owned(),
+                    old_order,
+                    new_order,
+                })
+            } else {
+                None
+            }
+        })
+        .collect();
+
+    let movie_changes = new_movies
+        .iter()
+        .filter_map(|new| {
+            let old = old_movies.iter().find(|old| old.id == new.id)?;
+            if old.handling != new.handling {
+                Some(MovieHandlingChange {
+                    id: new.id.to_owned(),
+                    old_handling: old.handling,
+                    new_handling: new.handling,
+                })
+            } else {
+                None
+            }
+        })
+        .collect();
+
+    Ok(ModToggleDiff {
+        items,
+        order_changes,
+        movie_changes,
+    })
+}
+
+/// Replaces load order entries referencing legacy mods by their pack name with the mod's id. Returns the replaced entries.
+#[tauri::command]
+async fn migrate_load_order_bin_names(
+    app: tauri::AppHandle,
+) -> Result<(Vec<(String, String)>, Vec<ListItem>), String> {
+    check_view_only()?;
+
+    let game_info = GAME_SELECTED.read().unwrap().clone();
+    let game_path = SETTINGS
+        .read()
+        .unwrap()
+        .game_path(&game_info)
+        .map_err(|e| format!("Error getting the game's path: {}", e))?;
+    let mut game_config = GAME_CONFIG
+        .lock()
+        .unwrap()
+        .clone()
+        .ok_or_else(|| "No game config loaded.".to_string())?;
+    let mut load_order = GAME_LOAD_ORDER.read().unwrap().clone();
+
+    let migrated = load_order.migrate_bin_names(&game_config);
+
+    let _ = game_config
+        .update_mod_list(&app, &game_info, &game_path, &mut load_order, true)
+        .await
+        .map_err(|e| format!("Error loading data: {}", e))?;
+    let items = load_packs(&app, &game_config, &game_info, &game_path, &load_order)
+        .await
+        .map_err(|e| format!("Error loading data: {}", e))?;
+
+    game_config
+        .save_or_defer(&app, &game_info)
+        .map_err(|e| format!("Error saving data: {}", e))?;
+
+    *GAME_LOAD_ORDER.write().unwrap() = load_order;
+    *GAME_CONFIG.lock().unwrap() = Some(game_config);
+
+    Ok((migrated, items))
+}
+
+#[tauri::command]
+fn snapshot_game_config(app: tauri::AppHandle, label: &str) -> Result<SnapshotInfo, String> {
+    let game_info = GAME_SELECTED.read().unwrap().clone();
+    let game_config = GAME_CONFIG
+        .lock()
+        .unwrap()
+        .clone()
+        .ok_or_else(|| "No game config loaded.".to_string())?;
+    let load_order = GAME_LOAD_ORDER.read().unwrap().clone();
+
+    let mut snapshot = Snapshot::new(&game_info, label, &game_config, &load_order)
+        .map_err(|e| format!("Error creating snapshot: {}", e))?;
+    snapshot
+        .save(&app)
+        .map_err(|e| format!("Error saving snapshot: {}", e))?;
+
+    Ok(SnapshotInfo {
+        label: snapshot.label().to_owned(),
+        timestamp: *snapshot.timestamp(),
+    })
+}
+
+#[tauri::command]
+fn list_snapshots(app: tauri::AppHandle) -> Result<Vec<SnapshotInfo>, String> {
+    let game_info = GAME_SELECTED.read().unwrap().clone();
+    let snapshots = Snapshot::snapshots_for_game(&app, &game_info)
+        .map_err(|e| format!("Error loading snapshots: {}", e))?;
+
+    Ok(snapshots
+        .iter()
+        .map(|snapshot| SnapshotInfo {
+            label: snapshot.label().to_owned(),
+            timestamp: *snapshot.timestamp(),
+        })
+        .collect())
+}
+
+#[tauri::command]
+async fn restore_snapshot(
+    app: tauri::AppHandle,
+    label: &str,
+) -> Result<(Vec<TreeCategory>, Vec<ListItem>), String> {
+    check_view_only()?;
+
+    let game_info = GAME_SELECTED.read().unwrap().clone();
+    let game_path = SETTINGS
+        .read()
+        .unwrap()
+        .game_path(&game_info)
+        .map_err(|e| format!("Error getting the game's path: {}", e))?;
+
+    let snapshot = Snapshot::latest_with_label(&app, &game_info, label)
+        .map_err(|e| format!("Error loading snapshot: {}", e))?;
+    let mut game_config = snapshot.game_config().clone();
+    let mut load_order = snapshot.load_order().clone();
+
+    // Mods may have been installed or removed since the snapshot was taken, so rescan them.
+    game_config
+        .update_mod_list(&app, &game_info, &game_path, &mut load_order, true)
+        .await
+        .map_err(|e| format!("Error loading data: {}", e))?;
+    let categories = load_mods(&app, &game_info, &game_config)
+        .await
+        .map_err(|e| format!("Error loading data: {}", e))?;
+    let items = load_packs(&app, &game_config, &game_info, &game_path, &load_order)
+        .await
+        .map_err(|e| format!("Error loading data: {}", e))?;
+
+    game_config
+        .save_or_defer(&app, &game_info)
+        .map_err(|e| format!("Error saving data: {}", e))?;
+
+    *GAME_LOAD_ORDER.write().unwrap() = load_order;
+    *GAME_CONFIG.lock().unwrap() = Some(game_config);
+
+    Ok((categories, items))
+}
+
+/// Regenerates the pack of a Shogun 2 map mod with the currently loaded schema. Returns the path of the regenerated pack.
+#[tauri::command]
+async fn regenerate_map_pack(app: tauri::AppHandle, mod_id: &str) -> Result<String, String> {
+    check_view_only()?;
+
+    let mod_id = unescape(mod_id);
+    let game_info = GAME_SELECTED.read().unwrap().clone();
+    let game_path = SETTINGS
+        .read()
+        .unwrap()
+        .game_path(&game_info)
+        .map_err(|e| format!("Error getting the game's path: {}", e))?;
+    let mut game_config = GAME_CONFIG
+        .lock()
+        .unwrap()
+        .clone()
+        .ok_or_else(|| "No game config loaded.".to_string())?;
+    let mut load_order = GAME_LOAD_ORDER.read().unwrap().clone();
+
+    let modd = game_config
+        .mods_mut()
+        .get_mut(&mod_id)
+        .ok_or_else(|| format!("Mod {} not found.", mod_id))?;
+    let pack_path = mod_manager::regenerate_map_pack(&app, &game_info, &game_path, modd)
+        .map_err(|e| format!("Error regenerating the map pack: {}", e))?;
+
+    let _ = game_config
+        .update_mod_list(&app, &game_info, &game_path, &mut load_order, true)
+        .await
+        .map_err(|e| format!("Error loading data: {}", e))?;
+
+    game_config
+        .save_or_defer(&app, &game_info)
+        .map_err(|e| format!("Error saving data: {}", e))?;
+
+    *GAME_LOAD_ORDER.write().unwrap() = load_order;
+    *GAME_CONFIG.lock().unwrap() = Some(game_config);
+
+    Ok(path_to_absolute_string(&pack_path))
+}
+
+#[tauri::command]
+async fn invert_enabled_mods(app: tauri::AppHandle) -> Result<Vec<ListItem>, String> {
+    check_view_only()?;
+
+    let game_info = GAME_SELECTED.read().unwrap().clone();
+    let game_path = SETTINGS.read().unwrap().game_path(&game_info).unwrap();
+    let game_data_path = game_info
+        .data_path(&game_path)
+        .map_err(|e| format!("Error getting the game's data path: {}", e))?;
+    let mut game_config = GAME_CONFIG.lock().unwrap().clone().unwrap();
+    let mut load_order = GAME_LOAD_ORDER.read().unwrap().clone();
+
+    // Only flip mods the user can actually toggle, so forced-on movie packs in /data stay on.
+    for modd in game_config.mods_mut().values_mut() {
+        if !modd.paths().is_empty() && modd.can_be_toggled(&game_info, &game_data_path) {
+            let enabled = modd.enabled(&game_info, &game_data_path);
+            modd.set_enabled(!enabled);
+        }
+    }
+
+    let _ = game_config
+        .update_mod_list(&app, &game_info, &game_path, &mut load_order, false)
+        .await
+        .map_err(|e| format!("Error loading data: {}", e))?;
+    let items = load_packs(&app, &game_config, &game_info, &game_path, &load_order)
+        .await
+        .map_err(|e| format!("Error loading data: {}", e))?;
+
+    game_config
+        .save_or_defer(&app, &game_info)
+        .map_err(|e| format!("Error saving data: {}", e))?;
+
+    *GAME_LOAD_ORDER.write().unwrap() = load_order;
+    *GAME_CONFIG.lock().unwrap() = Some(game_config);
+
+    Ok(items)
+}
+
+#[tauri::command]
+fn export_enable_states() -> Result<String, String> {
+    let game_info = GAME_SELECTED.read().unwrap().clone();
+    let game_path = SETTINGS
+        .read()
+        .unwrap()
+        .game_path(&game_info)
+        .map_err(|e| format!("Error getting the game's path: {}", e))?;
+    let game_data_path = game_info
+        .data_path(&game_path)
+        .map_err(|e| format!("Error getting the game's data path: {}", e))?;
+    let game_config = GAME_CONFIG
+        .lock()
+        .unwrap()
+        .clone()
+        .ok_or_else(|| "No game config loaded.".to_string())?;
+
+    let mut lines = game_config
+        .mods()
+        .values()
+        .filter(|modd| !modd.paths().is_empty())
+        .map(|modd| {
+            format!(
+                "{}={}",
+                modd.id(),
+                modd.enabled(&game_info, &game_data_path)
+            )
+        })
+        .collect::<Vec<_>>();
+    lines.sort();
+
+    Ok(lines.join("\n"))
+}
+
+#[tauri::command]
+async fn import_enable_states(app: tauri::AppHandle, text: &str) -> Result<Vec<ListItem>, String> {
+    check_view_only()?;
+
+    // Parse everything before touching the config, so a broken file doesn't apply half its changes.
+    let mut states = vec![];
+    for (index, line) in text.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        let (mod_id, enabled) = line
+            .rsplit_once('=')
+            .ok_or_else(|| format!("Invalid line {}: {}", index + 1, line))?;
+        let enabled = enabled
+            .trim()
+            .parse::<bool>()
+            .map_err(|_| format!("Invalid line {}: {}", index + 1, line))?;
+        states.push((mod_id.trim().to_owned(), enabled));
+    }
+
+    let game_info = GAME_SELECTED.read().unwrap().clone();
+    let game_path = SETTINGS
+        .read()
+        .unwrap()
+        .game_path(&game_info)
+        .map_err(|e| format!("Error getting the game's path: {}", e))?;
+    let game_data_path = game_info
+        .data_path(&game_path)
+        .map_err(|e| format!("Error getting the game's data path: {}", e))?;
+    let mut game_config = GAME_CONFIG
+        .lock()
+        .unwrap()
+        .clone()
+        .ok_or_else(|| "No game config loaded.".to_string())?;
+    let mut load_order = GAME_LOAD_ORDER.read().unwrap().clone();
+
+    // Mods not installed or that cannot be toggled are ignored.
+    for (mod_id, enabled) in states {
+        if let Some(modd) = game_config.mods_mut().get_mut(&mod_id) {
+            if !modd.paths().is_empty() && modd.can_be_toggled(&game_info, &game_data_path) {
+                modd.set_enabled(enabled);
+            }
+        }
+    }
+
+    game_config
+        .update_mod_list(&app, &game_info, &game_path, &mut load_order, false)
+        .await
+        .map_err(|e| format!("Error loading data: {}", e))?;
+    let items = load_packs(&app, &game_config, &game_info, &game_path, &load_order)
+        .await
+        .map_err(|e| format!("Error loading data: {}", e))?;
+
+    game_config
+        .save_or_defer(&app, &game_info)
+        .map_err(|e| format!("Error saving data: {}", e))?;
+
+    *GAME_LOAD_ORDER.write().unwrap() = load_order;
+    *GAME_CONFIG.lock().unwrap() = Some(game_config);
+
+    Ok(items)
+}
+
+#[tauri::command]
+fn handle_mod_category_change(
+    app: tauri::AppHandle,
+    mut mod_ids: Vec<String>,
+    category_id: &str,
+) -> Result<(), String> {
+    check_view_only()?;
+
+    let mod_ids = mod_ids
+        .iter_mut()
+        .map(|id| unescape(id))
+        .collect::<Vec<String>>();
+
+    let category_id = unescape(category_id);
+
+    let game_info = GAME_SELECTED.read().unwrap().clone();
+    let mut game_config = GAME_CONFIG.lock().unwrap().clone().unwrap();
+
+    // Only proceed if the category is valid.
+    if !game_config.categories().contains_key(&category_id) {
+        return Err(format!("Category {} not found", &category_id));
+    }
+
+    for mods in game_config.categories_mut().values_mut() {
+        mods.retain(|x| !mod_ids.contains(x));
+    }
+
+    if let Some(target_mods) = game_config.categories_mut().get_mut(&category_id) {
+        target_mods.extend(mod_ids);
+    }
+
+    game_config
+        .save_or_defer(&app, &game_info)
+        .map_err(|e| format!("Error saving data: {}", e))?;
+    *GAME_CONFIG.lock().unwrap() = Some(game_config);
+
+    Ok(())
+}
+
+#[tauri::command]
+async fn move_mod_to_category_at(
+    app: tauri::AppHandle,
+    mut mod_ids: Vec<String>,
+    category_id: &str,
+    index: usize,
+) -> Result<Vec<TreeCategory>, String> {
+    check_view_only()?;
+
+    let mod_ids = mod_ids
+        .iter_mut()
+        .map(|id| unescape(id))
+        .collect::<Vec<String>>();
+
+    let category_id = unescape(category_id);
+
+    let game_info = GAME_SELECTED.read().unwrap().clone();
+    let mut game_config = GAME_CONFIG
+        .lock()
+        .unwrap()
+        .clone()
+        .ok_or_else(|| "No game config loaded.".to_string())?;
+
+    game_config
+        .move_mods_to_category_at(&mod_ids, &category_id, index)
+        .map_err(|e| format!("Error moving mods: {}", e))?;
+
+    game_config
+        .save_or_defer(&app, &game_info)
+        .map_err(|e| format!("Error saving data: {}", e))?;
+
+    let categories = load_mods(&app, &game_info, &game_config)
+        .await
+        .map_err(|e| format!("Error loading data: {}", e))?;
+
+    *GAME_CONFIG.lock().unwrap() = Some(game_config);
+
+    Ok(categories)
+}
+
+#[tauri::command]
+fn set_mod_creator_name(app: tauri::AppHandle, mod_id: &str, name: &str) -> Result<(), String> {
+    check_view_only()?;
+
+    let mod_id = unescape(mod_id);
+    let game_info = GAME_SELECTED.read().unwrap().clone();
+    let mut game_config = GAME_CONFIG
+        .lock()
+        .unwrap()
+        .clone()
+        .ok_or_else(|| "No game config loaded.".to_string())?;
+
+    let modd = game_config
+        .mods_mut()
+        .get_mut(&mod_id)
+        .ok_or_else(|| format!("Mod {} not found.", mod_id))?;
+
+    // An empty name removes the override.
+    let name = name.trim();
+    modd.set_creator_name_override(if name.is_empty() {
+        None
+    } else {
+        Some(name.to_owned())
+    });
+
+    game_config
+        .save_or_defer(&app, &game_info)
+        .map_err(|e| format!("Error saving data: {}", e))?;
+    *GAME_CONFIG.lock().unwrap() = Some(game_config);
+
+    Ok(())
+}
+
+#[tauri::command]
+fn set_all_categories_open(
+    app: tauri::AppHandle,
+    open: bool,
+) -> Result<HashMap<String, bool>, String> {
+    let mut settings = SETTINGS.read().unwrap().clone();
+
+    // Include the categories of the current game, as the ones never toggled are not in the map yet.
+    //
+    // The UI keys them by the escaped id of the category element, so we need to build them the same way.
+    if let Some(ref game_config) = *GAME_CONFIG.lock().unwrap() {
+        for category in game_config.categories().keys() {
+            settings
+                .tree_open_state
+                .insert(escape(&("cat:".to_owned() + category)), open);
+        }
+    }
+
+    for state in settings.tree_open_state.values_mut() {
+        *state = open;
+    }
+
+    settings
+        .save(&app)
+        .map_err(|e| format!("Failed to save settings: {}", e))?;
+
+    let tree_open_state = settings.tree_open_state.clone();
+    *SETTINGS.write().unwrap() = settings;
+    Ok(tree_open_state)
+}
+
+#[tauri::command]
+fn init_settings(app_handle: tauri::AppHandle) -> Result<AppSettings, String> {
+    AppSettings::init(&app_handle).map_err(|e| format!("Failed to load settings: {}", e))
+}
+
+// Load settings from config file
+#[tauri::command]
+fn load_settings() -> Result<AppSettings, String> {
+    Ok(SETTINGS.read().unwrap().clone())
+}
+
+/// Discards the in-memory settings, replacing them with the ones in the settings file.
+#[tauri::command]
+fn reload_settings_from_disk(app_handle: tauri::AppHandle) -> Result<AppSettings, String> {
+    let settings =
+        AppSettings::load(&app_handle).map_err(|e| format!("Failed to reload settings: {}", e))?;
+    *SETTINGS.write().unwrap() = settings.clone();
+    Ok(settings)
+}
+
+// Save settings to config file
+#[tauri::command]
+fn save_settings(app_handle: tauri::AppHandle, mut settings: AppSettings) -> Result<(), String> {
+    // Keep the settings only the backend manages, as the UI doesn't send them.
+    let current_settings = SETTINGS.read().unwrap().clone();
+    settings.launch_presets = current_settings.launch_presets;
+    settings.disabled_mod_scripts = current_settings.disabled_mod_scripts;
+
+    settings
+        .save(&app_handle)
+        .map_err(|e| format!("Failed to save settings: {}", e))?;
+    *SETTINGS.write().unwrap() = settings;
+    Ok(())
+}
+
+#[tauri::command]
+fn set_filter_values(app: tauri::AppHandle, tree: &str, list: &str) -> Result<(), String> {
+    let mut settings = SETTINGS.read().unwrap().clone();
+    settings.tree_filter_value = tree.to_owned();
+    settings.list_filter_value = list.to_owned();
+
+    settings
+        .save(&app)
+        .map_err(|e| format!("Failed to save settings: {}", e))?;
+    *SETTINGS.write().unwrap() = settings;
+    Ok(())
+}
+
+#[tauri::command]
+fn set_open_remote_in_app(app: tauri::AppHandle, enabled: bool) -> Result<(), String> {
+    let mut settings = SETTINGS.read().unwrap().clone();
+    settings.open_remote_mod_in_app = enabled;
+
+    settings
+        .save(&app)
+        .map_err(|e| format!("Failed to save settings: {}", e))?;
+    *SETTINGS.write().unwrap() = settings;
+    Ok(())
+}
+
+#[tauri::command]
+fn get_selection_state() -> SelectionState {
+    let settings = SETTINGS.read().unwrap();
+    SelectionState {
+        tree_item: settings.selected_tree_item.clone(),
+        tree_category: settings.selected_tree_category.clone(),
+        list_item: settings.selected_list_item.clone(),
+    }
+}
+
+#[tauri::command]
+fn set_selection_state(
+    app: tauri::AppHandle,
+    tree_item: Option<String>,
+    tree_category: Option<String>,
+    list_item: Option<String>,
+) -> Result<(), String> {
+    let mut settings = SETTINGS.read().unwrap().clone();
+    settings.selected_tree_item = tree_item;
+    settings.selected_tree_category = tree_category;
+    settings.selected_list_item = list_item;
+
+    settings
+        .save(&app)
+        .map_err(|e| format!("Failed to save settings: {}", e))?;
+    *SETTINGS.write().unwrap() = settings;
+    Ok(())
+}
+
+#[tauri::command]
+fn get_available_languages() -> Vec<String> {
+    // Devuelve los idiomas disponibles en la aplicación
+    vec![
+        "English".to_string(),
+        "Español".to_string(),
+        "Français".to_string(),
+        "Deutsch".to_string(),
+        "Italiano".to_string(),
+    ]
+}
+
+#[tauri::command]
+fn get_available_date_formats() -> Vec<String> {
+    // Devuelve los formatos de fecha disponibles
+    vec![
+        "DD/MM/YYYY".to_string(),
+        "MM/DD/YYYY".to_string(),
+        "YYYY-MM-DD".to_string(),
+        "DD.MM.YYYY".to_string(),
+        "YYYY/MM/DD".to_string(),
+    ]
+}
+
+#[tauri::command]
+fn get_about_info(app: tauri::AppHandle) -> AboutInfo {
+    let schema_version = SCHEMA
+        .read()
+        .unwrap()
+        .as_ref()
+        .map(|schema| *schema.version());
+
+    let supported_games = SupportedGames::default()
+        .games()
+        .iter()
+        .filter(|game| game.key() != KEY_ARENA)
+        .count();
+
+    AboutInfo {
+        version: VERSION.to_string(),
+        version_subtitle: VERSION_SUBTITLE.to_string(),
+        schema_version,
+        sql_scripts_commit: sql_scripts_commit(&app),
+        supported_games,
+    }
+}
+
+/// Checks if the schema is older than the last update of the game.
+///
+/// Schemas don't contain the game version they were made for, so we use the dates to detect a stale schema.
+#[tauri::command]
+fn check_schema_compatibility(app: tauri::AppHandle) -> Result<SchemaCompatibility, String> {
+    let game = GAME_SELECTED.read().unwrap().clone();
+    let game_path = SETTINGS
+        .read()
+        .unwrap()
+        .game_path(&game)
+        .map_err(|e| format!("Error getting the game's path: {}", e))?;
+
+    let mut compatibility = SchemaCompatibility {
+        schema_loaded: SCHEMA.read().unwrap().is_some(),
+        ..Default::default()
+    };
+
+    if !compatibility.schema_loaded {
+        compatibility.warning = Some(
+            "No schema loaded. Features that depend on DB tables may produce wrong results."
+                .to_owned(),
+        );
+        return Ok(compatibility);
+    }
+
+    let schema_path = schemas_path(&app)
+        .map_err(|e| format!("Error getting the schemas path: {}", e))?
+        .join(game.schema_file_name());
+    compatibility.schema_date = schema_path
+        .metadata()
+        .and_then(|metadata| metadata.modified())
+        .ok()
+        .and_then(|date| date.duration_since(UNIX_EPOCH).ok())
+        .map(|date| date.as_secs())
+        .unwrap_or_default();
+
+    // If we can't get one of the dates, we can't tell if they diverge, so assume they don't.
+    compatibility.game_date = last_game_update_date(&game, &game_path).unwrap_or_default();
+    if compatibility.schema_date != 0
+        && compatibility.game_date != 0
+        && compatibility.game_date > compatibility.schema_date
+    {
+        compatibility.warning = Some(format!(
+            "The schema for {} is older than the last update of the game. Features that depend on DB tables, like map packs or conflict detection, may produce wrong results until the schema is updated.",
+            game.display_name()
+        ));
+    }
+
+    Ok(compatibility)
+}
+
+#[tauri::command]
+async fn browse_folder(
+    app: tauri::AppHandle,
+    title: String,
+    current_path: String,
+) -> Option<String> {
+    use std::path::PathBuf;
+    use tauri_plugin_dialog::DialogExt;
+
+    // Si se proporcionó una ruta válida, iniciar el diálogo en esa carpeta
+    let start_dir = PathBuf::from(&current_path);
+
+    // Mostrar el diálogo y obtener la carpeta seleccionada
+    let dialog = app
+        .dialog()
+        .file()
+        .set_directory(start_dir)
+        .set_title(title);
+
+    dialog
+        .blocking_pick_folder()
+        .map(|path| path.as_path().unwrap().to_string_lossy().to_string())
+}
+
+#[tauri::command]
+async fn open_mod_folder(id: String) -> Result<(), String> {
+    let mod_id = unescape(&id);
+
+    let game_config = GAME_CONFIG.lock().unwrap().clone().unwrap();
+    let mod_info = game_config.mods().get(&mod_id).unwrap();
+    match mod_info.paths().first().cloned() {
+        Some(mut path) => {
+            path.pop();
+            let _ = open::that(path);
+            Ok(())
+        }
+        None => Err("No path found".to_string()),
+    }
+}
+
+#[tauri::command]
+async fn open_sql_scripts_folder(app: tauri::AppHandle) -> Result<(), String> {
+    let game = GAME_SELECTED.read().unwrap().clone();
+    if game.key() == KEY_ARENA {
+        return Err("No game selected.".to_string());
+    }
+
+    let path = sql_scripts_local_path(&app)
+        .map_err(|e| format!("Error getting the config path: {}", e))?
+        .join(game.key());
+
+    DirBuilder::new()
+        .recursive(true)
+        .create(&path)
+        .map_err(|e| format!("Error creating the sql scripts folder: {}", e))?;
+
+    open::that(&path).map_err(|e| format!("Error opening the sql scripts folder: {}", e))
+}
+
+#[tauri::command]
+async fn open_mod_url(id: String) -> Result<(), String> {
+    let mod_id = unescape(&id);
+    if mod_id.is_empty() {
+        return Err("No mod ID found".to_string());
+    }
+
+    let game_config = GAME_CONFIG.lock().unwrap().clone().unwrap();
+    let mod_info = game_config.mods().get(&mod_id).unwrap();
+    let remote_id = mod_info.store_id();
+    let settings = SETTINGS.read().unwrap().clone();
+
+    Integrations::open_remote_mod_url(&remote_id, settings.open_remote_mod_in_app)
+        .map_err(|e| format!("Error opening mod URL: {}", e))
+}
+
+#[tauri::command]
+async fn handle_change_game_selected(
+    app: tauri::AppHandle,
+    game_id: String,
+) -> Result<(Vec<TreeCategory>, Vec<ListItem>), String> {
+    let old_game = GAME_SELECTED.read().unwrap().clone();
+    let old_game_id = old_game.key();
+    change_game_selected(app, &game_id, old_game_id == game_id, false)
+        .await
+        .map_err(|e| format!("Error loading data: {}", e))
+}
+
+async fn change_game_selected(
+    app: tauri::AppHandle,
+    game_id: &str,
+    reload_same_game: bool,
+    skip_network_update: bool,
+) -> Result<(Vec<TreeCategory>, Vec<ListItem>), String> {
+    let old_game = GAME_SELECTED.read().unwrap().clone();
+    let old_game_id = old_game.key();
+    use futures_util::TryFutureExt;
+    // If the game changed or we're initializing the program, change the game selected.
+    //
+    // This works because by default, the initially stored game selected is arena, and that one can never set manually.
+    if reload_same_game || game_id != old_game_id {
+        let result = load_data(&app, &game_id, skip_network_update);
+        result
+            .map_err(|e| format!("Error loading data: {}", e))
+            .await
+    } else {
+        Ok((vec![], vec![]))
+    }
+}
+
+async fn load_data(
+    app: &tauri::AppHandle,
+    game_id: &str,
+    skip_network_update: bool,
+) -> anyhow::Result<(Vec<TreeCategory>, Vec<ListItem>)> {
+    // We may receive invalid games here, so rule out the invalid ones.
+    let supported_games = SupportedGames::default();
+    match supported_games.game(game_id) {
+        Some(game) => {
+            // Schemas are optional, so don't interrupt loading due to they not being present.
+            *SCHEMA.write().unwrap() = schemas_path(app)
+                .ok()
+                .and_then(|path| Schema::load(&path.join(game.schema_file_name()), None).ok());
+
+            // The previous game's config is about to be replaced, so save it if it has pending changes.
+            save_unsaved_changes(app)?;
+            *GAME_SELECTED.write().unwrap() = game.clone();
+
+            let mut timings = LoadTimings {
+                game: game.key().to_owned(),
+                ..Default::default()
+            };
+            let total_start = Instant::now();
+            let start = Instant::now();
+
+            // Trigger an update of all game configs, just in case one needs update.
+            let _ = GameConfig::update(game.key());
+
+            // Load the game's config and last known load order.
+            let mut load_order = LoadOrder::load(app, game).unwrap_or_else(|_| Default::default());
+            let mut game_config = GameConfig::load(app, game, true)?;
+
+            let settings = SETTINGS.read().unwrap().clone();
+            let game_path = settings.game_path(game)?;
+
+            *GAME_LOAD_ORDER.write().unwrap() = load_order;
+            *GAME_CONFIG.lock().unwrap() = Some(game_config.clone());
+
+            // Trigger an update of all game profiles, just in case one needs update.
+            let _ = Profile::update(&game_config, game);
+
+            // Load the profile's list.
+            match Profile::profiles_for_game(app, game) {
+                Ok(profiles) => *GAME_PROFILES.write().unwrap() = profiles,
+                Err(error) => return Err(anyhow!("Error loading profiles: {}", error)),
+            }
+
+            timings.config_load = start.elapsed().as_millis() as u64;
+            /*
+                        self.actions_ui().profile_model().clear();
+                        for profile in self.game_profiles().read().unwrap().keys().sorted() {
+                            self.actions_ui().profile_combobox().add_item_q_string(&QString::from_std_str(profile));
+                        }
+
+                        // Load the saves list for the selected game.
+                        let game_path_str = setting_string(game.key());
+                        let game_path = PathBuf::from(&game_path_str);
+                        if let Err(error) = self.load_saves_to_ui(game, &game_path) {
+                            show_dialog(self.main_window(), error, false);
+                        }
+            */
+
+            send_progress_event(&app, 10, 100);
+
+            let mut load_order = GAME_LOAD_ORDER.read().unwrap().clone();
+            let online_data_receiver = game_config
+                .update_mod_list(app, &game, &game_path, &mut load_order, skip_network_update)
+                .await?;
+
+            let scan_timings = game_config.scan_timings();
+            timings.content_scan = scan_timings.content_scan();
+            timings.online_request = scan_timings.online_request();
+            timings.secondary_scan = scan_timings.secondary_scan();
+            timings.data_scan = scan_timings.data_scan();
+
+            send_progress_event(&app, 30, 100);
+
+            // Online data may take a while, so don't make the user wait for it. Load it in the background and let the UI merge it when it's done.
+            if let Some(tx_recv) = online_data_receiver {
+                let app = app.clone();
+                let game_config = game_config.clone();
+                tauri::async_runtime::spawn(async move {
+                    if let Err(error) = load_online_data(&app, game_config, tx_recv).await {
+                        error!("Error loading online data: {}", error);
+                    }
+                });
+            }
+
+            send_progress_event(&app, 50, 100);
+            let start = Instant::now();
+            let mods = load_mods(&app, &game, &game_config).await?;
+            timings.mod_tree_loading = start.elapsed().as_millis() as u64;
+
+            send_progress_event(&app, 70, 100);
+            let start = Instant::now();
+            let items = load_packs(&app, &game_config, &game, &game_path, &load_order).await?;
+            timings.pack_loading = start.elapsed().as_millis() as u64;
+
+            send_progress_event(&app, 90, 100);
+            *GAME_LOAD_ORDER.write().unwrap() = load_order;
+            *GAME_CONFIG.lock().unwrap() = Some(game_config.clone());
+
+            timings.total = total_start.elapsed().as_millis() as u64;
+            *LAST_LOAD_TIMINGS.write().unwrap() = Some(timings);
+
+            send_progress_event(&app, 100, 100);
+
+            Ok((mods, items))
+        }
+        None => Err(anyhow!("Game {} is not a valid game.", game_id)),
+    }
+}
+
+/// Populates the mods with online data, merges them into the current game config, and sends the changed mods to the UI.
+async fn load_online_data(
+    app: &tauri::AppHandle,
+    mut game_config: GameConfig,
+    tx_recv: tauri::async_runtime::Receiver<TxStoreResponse>,
+) -> anyhow::Result<()> {
+    let start = Instant::now();
+    let game = GAME_SELECTED.read().unwrap().clone();
+
+    // NOTE: THIS CAN FAIL AND IT NEEDS TO NOT FAIL THE ENTIRE LOAD.
+    game_config
+        .update_mod_list_with_online_data(tx_recv, app)
+        .await?;
+
+    // If the user changed the game while we were waiting, this data is no longer needed.
+    if GAME_SELECTED.read().unwrap().key() != game.key() {
+        return Ok(());
+    }
+
+    let mut current_config = GAME_CONFIG
+        .lock()
+        .unwrap()
+        .clone()
+        .ok_or_else(|| anyhow!("No game config loaded."))?;
+    let old_config = current_config.clone();
+    current_config.merge_online_data(game_config);
+    current_config.save_or_defer(app, &game)?;
+    *GAME_CONFIG.lock().unwrap() = Some(current_config.clone());
+
+    let old_items = tree_items_by_id(load_mods(app, &game, &old_config).await?);
+    let new_items = tree_items_by_id(load_mods(app, &game, &current_config).await?);
+    let update = ModTreeUpdate {
+        removed: old_items
+            .keys()
+            .filter(|id| !new_items.contains_key(*id))
+            .cloned()
+            .collect(),
+        changed: new_items
+            .into_iter()
+            .filter(|(id, item)| old_items.get(id) != Some(item))
+            .collect(),
+    };
+
+    if let Some(timings) = LAST_LOAD_TIMINGS.write().unwrap().as_mut() {
+        if timings.game == game.key() {
+            timings.online_fetch = start.elapsed().as_millis() as u64;
+        }
+    }
+
+    let _ = app.emit("mods://metadata_updated", update);
+
+    Ok(())
+}
+
+/// Flattens a mod tree into its items, by id.
+fn tree_items_by_id(categories: Vec<TreeCategory>) -> BTreeMap<String, TreeItem> {
+    categories
+        .into_iter()
+        .flat_map(|category| category.children)
+        .map(|item| (item.id.to_owned(), item))
+        .collect()
+}
+
+/// Rescans the mods of the current game and requests their online data again, for when the store failed during the load.
+#[tauri::command]
+async fn refresh_all_online_metadata(
+    app: tauri::AppHandle,
+) -> Result<(Vec<TreeCategory>, Vec<ListItem>), String> {
+    check_view_only()?;
+
+    let game_info = GAME_SELECTED.read().unwrap().clone();
+    let game_path = SETTINGS
+        .read()
+        .unwrap()
+        .game_path(&game_info)
+        .map_err(|e| format!("Error getting the game's path: {}", e))?;
+    let mut game_config = GAME_CONFIG
+        .lock()
+        .unwrap()
+        .clone()
+        .ok_or_else(|| "No game config loaded.".to_string())?;
+    let mut load_order = GAME_LOAD_ORDER.read().unwrap().clone();
+
+    send_progress_event(&app, 10, 100);
+    let online_data_receiver = game_config
+        .update_mod_list(&app, &game_info, &game_path, &mut load_order, false)
+        .await
+        .map_err(|e| format!("Error loading data: {}", e))?;
+
+    // Unlike on load, wait for the online data, so the user knows when the refresh is done.
+    send_progress_event(&app, 30, 100);
+    if let Some(tx_recv) = online_data_receiver {
+        game_config
+            .update_mod_list_with_online_data(tx_recv, &app)
+            .await
+            .map_err(|e| format!("Error loading online data: {}", e))?;
+    }
+
+    send_progress_event(&app, 70, 100);
+    let mods = load_mods(&app, &game_info, &game_config)
+        .await
+        .map_err(|e| format!("Error loading data: {}", e))?;
+    let items = load_packs(&app, &game_config, &game_info, &game_path, &load_order)
+        .await
+        .map_err(|e| format!("Error loading data: {}", e))?;
+
+    send_progress_event(&app, 90, 100);
+    game_config
+        .save_or_defer(&app, &game_info)
+        .map_err(|e| format!("Error saving data: {}", e))?;
+
+    *GAME_LOAD_ORDER.write().unwrap() = load_order;
+    *GAME_CONFIG.lock().unwrap() = Some(game_config);
+
+    send_progress_event(&app, 100, 100);
+
+    Ok((mods, items))
+}
+
+async fn load_mods(
+    app: &tauri::AppHandle,
+    game: &GameInfo,
+    game_config: &GameConfig,
+) -> anyhow::Result<Vec<TreeCategory>> {
+    use crate::mod_manager::secondary_mods_path;
+    use rpfm_lib::utils::path_to_absolute_string;
+    use std::time::UNIX_EPOCH;
+
+    let settings = SETTINGS.read().unwrap().clone();
+    let game_path = settings.game_path(game)?;
+    let game_last_update_date = last_game_update_date(game, &game_path)?;
+    let game_data_path = game.data_path(&game_path)?;
+
+    let data_path = path_to_absolute_string(&game_data_path);
+    let secondary_path =
+        path_to_absolute_string(&secondary_mods_path(app, game.key()).unwrap_or_default());
+    let content_path = path_to_absolute_string(&game.content_path(&game_path).unwrap_or_default());
+    /*
+        // Initialize these here so they can be re-use.
+        let outdated_icon = icon_data("outdated.png").unwrap_or_else(|_| vec![]);
+        let outdated = tre("mod_outdated_description", &[&BASE64_STANDARD.encode(outdated_icon)]);
+
+        let data_older_than_secondary_icon = icon_data("data_older_than_secondary.png").unwrap_or_else(|_| vec![]);
+        let data_older_than_secondary = tre("mod_data_older_than_secondary", &[&BASE64_STANDARD.encode(data_older_than_secondary_icon)]);
+
+        let data_older_than_content_icon = icon_data("data_older_than_content.png").unwrap_or_else(|_| vec![]);
+        let data_older_than_content = tre("mod_data_older_than_content", &[&BASE64_STANDARD.encode(data_older_than_content_icon)]);
+
+        let secondary_older_than_content_icon = icon_data("secondary_older_than_content.png").unwrap_or_else(|_| vec![]);
+        let secondary_older_than_content = tre("mod_secondary_older_than_content", &[&BASE64_STANDARD.encode(secondary_older_than_content_icon)]);
+    */
+    // This loads mods per category, meaning all installed mod have to be in the categories list!!!!
+    let mut categories: Vec<TreeCategory> = vec![];
+    for category in game_config.categories_order() {
+        let mut cat_item = TreeCategory::default();
+        cat_item.id = "cat:".to_owned() + category;
+        cat_item.name = category.to_string();
+
+        if let Some(mods) = game_config.categories().get(category) {
+            for mod_id in mods {
+                if let Some(modd) = game_config.mods().get(mod_id) {
+                    // Ignore registered mods with no path.
+                    if !modd.paths().is_empty() {
+                        let mut item = TreeItem::default();
+                        item.id = "mod:".to_owned() + mod_id;
+                        item.name = if modd.name() != modd.id() {
+                            if !modd.file_name().is_empty() {
+                                // Map filenames are folder names which we have to turn into packs.
+                                let pack_name = if let Some(alt_name) = modd.alt_name() {
+                                    alt_name.to_string()
+                                } else {
+                                    modd.file_name().split('/').last().unwrap().to_owned()
+                                };
+
+                                format!(
+                                    "<b>{}</b> <i>({} - {})</i>",
+                                    modd.name(),
+                                    pack_name,
+                                    modd.id()
+                                )
+                            } else {
+                                format!("<b>{}</b> <i>({})</i>", modd.name(), modd.id())
+                            }
+                        } else {
+                            format!("<i>{}</i>", modd.name())
+                        };
+
+                        item.creator = modd.display_creator_name().to_owned();
+                        item.r#type = modd.pack_type().to_string();
+                        item.description = modd.description().to_owned();
+
+                        // TODO: show discrepancies between steam's reported data and real data.
+                        item.size = if *modd.file_size() != 0 {
+                            format!("{:.2} MB", *modd.file_size() as f64 / 1024.0 / 1024.0)
+                        } else {
+                            let size = modd.paths()[0].metadata()?.len();
+                            format!("{:.2} MB", size as f64 / 1024.0 / 1024.0)
+                        };
+
+                        item.created = if *modd.time_created() != 0 {
+                            *modd.time_created() as u64
+                        } else if cfg!(target_os = "windows") {
+                            let date = modd.paths()[0]
+                                .metadata()?
+                                .created()?
+                                .duration_since(UNIX_EPOCH)?;
+                            date.as_secs() as u64
+                        } else {
+                            0
+                        };
+
+                        item.updated = *modd.time_updated() as u64;
+
+                        let mut flags_description = vec![];
+                        item.is_outdated = modd.outdated(game_last_update_date);
+                        if item.is_outdated {
+                            flags_description.push("The mod is older than the last update of the game. It may not work properly.");
+                        }
+
+                        if let Ok(flags) =
+                            modd.priority_dating_flags(&data_path, &secondary_path, &content_path)
+                        {
+                            item.data_older_than_secondary = flags.0;
+                            item.data_older_than_content = flags.1;
+                            item.secondary_older_than_content = flags.2;
+
+                            if flags.0 {
+                                flags_description.push("The copy in /data is older than the one in the secondary folder, but it's the one being loaded.");
+                            }
+
+                            if flags.1 {
+                                flags_description.push("The copy in /data is older than the one in the content folder, but it's the one being loaded.");
+                            }
+
+                            if flags.2 {
+                                flags_description.push("The copy in the secondary folder is older than the one in the content folder, but it's the one being loaded.");
+                            }
+                        }
+
+                        item.flags = flags_description.join("\n");
+
+                        let (l_data, l_secondary, l_content) =
+                            modd.location(&data_path, &secondary_path, &content_path);
+                        let mut locations = vec![];
+
+                        if l_data {
+                            locations.push("Data".to_owned());
+                        }
+                        if l_secondary {
+                            locations.push("Secondary".to_owned());
+                        }
+
+                        match l_content {
+                            StoreId::None => {},
+                            StoreId::Steam(id) |
+                            StoreId::Epic(id) |
+                            StoreId::Nexus(id) |
+                            StoreId::ModDB(id) |
+                            StoreId::LoversLab(id) |
+                            StoreId::Github(id) => {
+                                locations.push(format!("Content ({})", id));
+                            }
+                        }
+
+                        item.location = locations.join(",");
+
+                        /*
+                        if modd.can_be_toggled(game, &game_data_path) {
+                            item_mod_name.set_checkable(true);
+
+                            if modd.enabled(game, &game_data_path) {
+                                item_mod_name.set_check_state(CheckState::Checked);
+                            }
+                        }
+
+                        // This is for movie mods in /data.
+                        else {
+                            item_mod_name.set_checkable(true);
+                            item_mod_name.set_check_state(CheckState::Checked);
+
+                            let mut flags = item_mod_name.flags().to_int();
+                            flags &= !ItemFlag::ItemIsUserCheckable.to_int();
+                            item_mod_name.set_flags(QFlags::from(flags));
+                        }*/
+
+                        item.is_checked = modd.enabled(game, &game_data_path);
+
+                        cat_item.children.push(item);
+                    }
+                }
+            }
+        }
+        categories.push(cat_item);
+    }
+
+    Ok(categories)
+}
+
+async fn load_packs(
+    app: &tauri::AppHandle,
+    game_config: &GameConfig,
+    game_info: &GameInfo,
+    game_path: &Path,
+    load_order: &LoadOrder,
+) -> anyhow::Result<Vec<ListItem>> {
+    load_pack_list(app, game_config, game_info, game_path, load_order, false).await
+}
+
+/// Builds the pack list. If requested, disabled mods that can be toggled are added at the end with order -1.
+async fn load_pack_list(
+    app: &tauri::AppHandle,
+    game_config: &GameConfig,
+    game_info: &GameInfo,
+    game_path: &Path,
+    load_order: &LoadOrder,
+    include_disabled: bool,
+) -> anyhow::Result<Vec<ListItem>> {
+    use crate::mod_manager::secondary_mods_path;
+    use rpfm_lib::files::pack::Pack;
+
+    let mut items = vec![];
+
+    let secondary_mods_path = secondary_mods_path(app, game_config.game_key()).unwrap_or_default();
+    if !game_path.to_string_lossy().is_empty() {
+        if let Ok(game_data_folder) = game_info.data_path(game_path) {
+            let game_data_folder = std::fs::canonicalize(game_data_folder.clone())
+                .unwrap_or_else(|_| game_data_folder.clone());
+
+            // Chain so movie packs are always last.
+            let mut mods = load_order
+                .mods()
+                .iter()
+                .chain(load_order.movies().iter())
+                .enumerate()
+                .map(|(index, mod_id)| (mod_id, index as i32))
+                .collect::<Vec<_>>();
+
+            if include_disabled {
+                let mut disabled = game_config
+                    .mods()
+                    .values()
+                    .filter(|modd| {
+                        !modd.paths().is_empty()
+                            && modd.can_be_toggled(game_info, &game_data_folder)
+                            && !modd.enabled(game_info, &game_data_folder)
+                    })
+                    .map(|modd| (modd.id(), -1))
+                    .collect::<Vec<_>>();
+                disabled.sort();
+                mods.extend(disabled);
+            }
+
+            for (mod_id, order) in mods {
+                if let Some(modd) = game_config.mods().get(mod_id) {
+                    let pack_name = modd.paths()[0]
+                        .file_name()
+                        .unwrap()
+                        .to_string_lossy()
+                        .as_ref()
+                        .to_owned();
+
+                    // This is needed to avoid errors with map packs before we process them.
+                    //
+                    // In practice if a bin pack loads here, there's a bug elsewhere.
+                    if pack_name.ends_with(".pack") {
+                        let pack = Pack::read_and_merge(
+                            &[modd.paths()[0].to_path_buf()],
+                            true,
+                            false,
+                            false,
+                            false,
+                        )?;
+
+                        let mut item = ListItem::default();
+                        item.id = mod_id.to_string();
+                        item.pack = pack_name;
+                        item.r#type = modd.pack_type().to_string();
+                        item.order = order;
+                        item.enabled = order >= 0;
+                        item.location = if modd.paths()[0].starts_with(&game_data_folder) {
+                            "Data".to_string()
+                        } else if secondary_mods_path.is_dir()
+                            && modd.paths()[0].starts_with(&secondary_mods_path)
+                        {
+                            format!("Secondary ({})", match modd.store_id() {
+                                StoreId::None => "Local",
+                                StoreId::Steam(ref id) => id,
+                                StoreId::Epic(ref id) => id,
+                                StoreId::Nexus(ref id) => id,
+                                StoreId::ModDB(ref id) => id,
+                                StoreId::LoversLab(ref id) => id,
+                                StoreId::Github(ref id) => id,
+                            })
+                        } else if let StoreId::None = modd.store_id() {
+                            "Where the fuck is this pack?".to_string()
+                        } else {
+                            format!("Content ({})", match modd.store_id() {
+                                StoreId::None => "Local",
+                                StoreId::Steam(ref id) => id,
+                                StoreId::Epic(ref id) => id,
+                                StoreId::Nexus(ref id) => id,
+                                StoreId::ModDB(ref id) => id,
+                                StoreId::LoversLab(ref id) => id,
+                                StoreId::Github(ref id) => id,
+                            })
+                        };
+
+                        //item.store_id = modd.store_id().clone();
+                        items.push(item);
+                    } else {
+                        // TODO: fix this case in shogun 2. Also seen up to warhammer 1....
+                        //error!("Error loading Pack to UI: {}", modd.paths()[0].to_string_lossy())
+                    }
+                }
+            }
+        }
+    }
+
+    Ok(items)
+}
+
+#[tauri::command]
+async fn load_packs_with_disabled(app: tauri::AppHandle) -> Result<Vec<ListItem>, String> {
+    let game_info = GAME_SELECTED.read().unwrap().clone();
+    let game_path = SETTINGS
+        .read()
+        .unwrap()
+        .game_path(&game_info)
+        .map_err(|e| format!("Error getting the game's path: {}", e))?;
+    let game_config = GAME_CONFIG
+        .lock()
+        .unwrap()
+        .clone()
+        .ok_or_else(|| "No game config loaded.".to_string())?;
+    let load_order = GAME_LOAD_ORDER.read().unwrap().clone();
+
+    load_pack_list(
+        &app,
+        &game_config,
+        &game_info,
+        &game_path,
+        &load_order,
+        true,
+    )
+    .await
+    .map_err(|e| format!("Error loading packs: {}", e))
+}
+
+#[tauri::command]
+async fn move_pack_in_load_order_in_direction(
+    app: tauri::AppHandle,
+    mod_id: &str,
+    direction: LoadOrderDirectionMove,
+) -> Result<Vec<ListItem>, String> {
+    check_view_only()?;
+
+    let game_info = GAME_SELECTED.read().unwrap().clone();
+    let game_path = SETTINGS.read().unwrap().game_path(&game_info).unwrap();
+    let game_config = GAME_CONFIG.lock().unwrap().clone().unwrap();
+    let mut load_order = GAME_LOAD_ORDER.read().unwrap().clone();
+    let mod_id = unescape(mod_id);
+
+    load_order.move_mod_in_direction(&mod_id, direction);
+    let items = load_packs(&app, &game_config, &game_info, &game_path, &load_order)
+        .await
+        .map_err(|e| format!("Error loading data: {}", e))?;
+
+    *GAME_LOAD_ORDER.write().unwrap() = load_order;
+
+    Ok(items)
+}
+
+#[tauri::command]
+async fn move_pack_in_load_order(
+    app: tauri::AppHandle,
+    source_id: &str,
+    target_id: &str,
+) -> Result<Vec<ListItem>, String> {
+    check_view_only()?;
+
+    let game_info = GAME_SELECTED.read().unwrap().clone();
+    let game_path = SETTINGS.read().unwrap().game_path(&game_info).unwrap();
+    let game_config = GAME_CONFIG.lock().unwrap().clone().unwrap();
+    let mut load_order = GAME_LOAD_ORDER.read().unwrap().clone();
+    let source_id = unescape(source_id);
+    let target_id = unescape(target_id);
+
+    load_order.move_mod_above_another(&source_id, &target_id);
+    let items = load_packs(&app, &game_config, &game_info, &game_path, &load_order)
+        .await
+        .map_err(|e| format!("Error loading data: {}", e))?;
+
+    *GAME_LOAD_ORDER.write().unwrap() = load_order;
+
+    Ok(items)
+}
+
+#[tauri::command]
+async fn move_movie_above_another(
+    app: tauri::AppHandle,
+    source_id: &str,
+    target_id: &str,
+) -> Result<Vec<ListItem>, String> {
+    check_view_only()?;
+
+    let game_info = GAME_SELECTED.read().unwrap().clone();
+    if game_uses_movie_masking(&game_info) {
+        return Err(format!(
+            "{} doesn't support reordering movie packs.",
+            game_info.display_name()
+        ));
+    }
+
+    let game_path = SETTINGS
+        .read()
+        .unwrap()
+        .game_path(&game_info)
+        .map_err(|e| format!("Error getting the game's path: {}", e))?;
+    let game_config = GAME_CONFIG
+        .lock()
+        .unwrap()
+        .clone()
+        .ok_or_else(|| "No game config loaded.".to_string())?;
+    let mut load_order = GAME_LOAD_ORDER.read().unwrap().clone();
+    let source_id = unescape(source_id);
+    let target_id = unescape(target_id);
+
+    load_order.move_movie_above_another(&source_id, &target_id);
+    let items = load_packs(&app, &game_config, &game_info, &game_path, &load_order)
+        .await
+        .map_err(|e| format!("Error loading data: {}", e))?;
+
+    *GAME_LOAD_ORDER.write().unwrap() = load_order;
+
+    Ok(items)
+}
+
+#[tauri::command]
+async fn set_manual_movie_order(
+    app: tauri::AppHandle,
+    manual: bool,
+) -> Result<Vec<ListItem>, String> {
+    check_view_only()?;
+
+    let game_info = GAME_SELECTED.read().unwrap().clone();
+    let game_path = SETTINGS
+        .read()
+        .unwrap()
+        .game_path(&game_info)
+        .map_err(|e| format!("Error getting the game's path: {}", e))?;
+    let game_data_path = game_info
+        .data_path(&game_path)
+        .map_err(|e| format!("Error getting the game's data path: {}", e))?;
+    let game_config = GAME_CONFIG
+        .lock()
+        .unwrap()
+        .clone()
+        .ok_or_else(|| "No game config loaded.".to_string())?;
+    let mut load_order = GAME_LOAD_ORDER.read().unwrap().clone();
+
+    // Rebuild the order, so going back to automatic sorts the movies again.
+    load_order.set_manual_movies(manual);
+    load_order.update(&app, &game_config, &game_info, &game_data_path);
+    load_order
+        .save_or_defer(&app, &game_info)
+        .map_err(|e| format!("Error saving data: {}", e))?;
+
+    let items = load_packs(&app, &game_config, &game_info, &game_path, &load_order)
+        .await
+        .map_err(|e| format!("Error loading data: {}", e))?;
+
+    *GAME_LOAD_ORDER.write().unwrap() = load_order;
+
+    Ok(items)
+}
+
+/// Returns the packs required by the loaded mods, and the installed mod providing each one, if any.
+///
+/// Requirements can be satisfied by the pack name of a mod, or by the converted pack name of a legacy mod.
+#[tauri::command]
+fn get_requirement_satisfaction() -> Result<Vec<ModRequirement>, String> {
+    let game = GAME_SELECTED.read().unwrap().clone();
+    let game_path = SETTINGS
+        .read()
+        .unwrap()
+        .game_path(&game)
+        .map_err(|e| format!("Error getting the game's path: {}", e))?;
+    let game_config = GAME_CONFIG
+        .lock()
+        .unwrap()
+        .clone()
+        .ok_or_else(|| "No game config loaded.".to_string())?;
+    let load_order = GAME_LOAD_ORDER.read().unwrap();
+
+    // Mods may also depend on vanilla packs.
+    let vanilla_packs = game
+        .ca_packs_paths(&game_path)
+        .unwrap_or_default()
+        .iter()
+        .filter_map(|path| Some(path.file_name()?.to_string_lossy().to_string()))
+        .collect::<Vec<_>>();
+
+    let provider = |requirement: &str| {
+        if vanilla_packs.iter().any(|pack| pack == requirement) {
+            return Some(requirement.to_owned());
+        }
+
+        game_config
+            .mods()
+            .values()
+            .filter(|modd| !modd.paths().is_empty())
+            .find(|modd| {
+                modd.id() == requirement || modd.alt_name().is_some_and(|name| name == requirement)
+            })
+            .map(|modd| modd.id().to_owned())
+    };
+
+    let mut requirements = vec![];
+    for mod_id in load_order.mods().iter().chain(load_order.movies().iter()) {
+        if let Some(pack) = load_order.packs().get(mod_id) {
+            for (_, requirement) in pack.dependencies() {
+                requirements.push(ModRequirement {
+                    id: mod_id.to_owned(),
+                    requirement: requirement.to_owned(),
+                    provided_by: provider(requirement),
+                });
+            }
+        }
+    }
+
+    Ok(requirements)
+}
+
+/// Returns a suggested order for the loaded mods, based on their dependencies and files. It's only a preview, it's not applied.
+#[tauri::command]
+fn suggest_load_order() -> Vec<String> {
+    GAME_LOAD_ORDER.read().unwrap().suggested_order()
+}
+
+/// Applies a load order, like the suggested one, as a manual order. It must contain exactly the currently enabled mods.
+#[tauri::command]
+async fn apply_suggested_load_order(
+    app: tauri::AppHandle,
+    order: Vec<String>,
+) -> Result<Vec<ListItem>, String> {
+    check_view_only()?;
+
+    let order = order
+        .iter()
+        .map(|mod_id| unescape(mod_id))
+        .collect::<Vec<_>>();
+    let game_info = GAME_SELECTED.read().unwrap().clone();
+    let game_path = SETTINGS
+        .read()
+        .unwrap()
+        .game_path(&game_info)
+        .map_err(|e| format!("Error getting the game's path: {}", e))?;
+    let game_data_path = game_info
+        .data_path(&game_path)
+        .map_err(|e| format!("Error getting the game's data path: {}", e))?;
+    let game_config = GAME_CONFIG
+        .lock()
+        .unwrap()
+        .clone()
+        .ok_or_else(|| "No game config loaded.".to_string())?;
+    let mut load_order = GAME_LOAD_ORDER.read().unwrap().clone();
+
+    let mut new_mods = order.clone();
+    let mut enabled_mods = load_order.mods().clone();
+    new_mods.sort();
+    enabled_mods.sort();
+    if new_mods != enabled_mods {
+        return Err(
+            "The load order doesn't match the enabled mods. Mods cannot be added or removed when applying a load order."
+                .to_string(),
+        );
+    }
+
+    load_order.set_automatic(false);
+    load_order.set_mods(order);
+    load_order.update(&app, &game_config, &game_info, &game_data_path);
+    load_order
+        .save_or_defer(&app, &game_info)
+        .map_err(|e| format!("Error saving data: {}", e))?;
+
+    let items = load_packs(&app, &game_config, &game_info, &game_path, &load_order)
+        .await
+        .map_err(|e| format!("Error loading data: {}", e))?;
+
+    *GAME_LOAD_ORDER.write().unwrap() = load_order;
+
+    Ok(items)
+}
+
+#[tauri::command]
+fn is_load_order_automatic() -> bool {
+    *GAME_LOAD_ORDER.read().unwrap().automatic()
+}
+
+#[tauri::command]
+async fn set_load_order_automatic(app: tauri::AppHandle) -> Result<Vec<ListItem>, String> {
+    set_load_order_mode(&app, true).await
+}
+
+#[tauri::command]
+async fn set_load_order_manual(app: tauri::AppHandle) -> Result<Vec<ListItem>, String> {
+    set_load_order_mode(&app, false).await
+}
+
+/// Switches the load order between automatic and manual. Going back to automatic discards the manual order.
+async fn set_load_order_mode(
+    app: &tauri::AppHandle,
+    automatic: bool,
+) -> Result<Vec<ListItem>, String> {
+    check_view_only()?;
+
+    let game_info = GAME_SELECTED.read().unwrap().clone();
+    let game_path = SETTINGS
+        .read()
+        .unwrap()
+        .game_path(&game_info)
+        .map_err(|e| format!("Error getting the game's path: {}", e))?;
+    let game_data_path = game_info
+        .data_path(&game_path)
+        .map_err(|e| format!("Error getting the game's data path: {}", e))?;
+    let game_config = GAME_CONFIG
+        .lock()
+        .unwrap()
+        .clone()
+        .ok_or_else(|| "No game config loaded.".to_string())?;
+    let mut load_order = GAME_LOAD_ORDER.read().unwrap().clone();
+
+    // The automatic build ignores the current order, so the manual one is lost here.
+    load_order.set_automatic(automatic);
+    load_order.update(app, &game_config, &game_info, &game_data_path);
+    load_order
+        .save_or_defer(app, &game_info)
+        .map_err(|e| format!("Error saving data: {}", e))?;
+
+    let items = load_packs(app, &game_config, &game_info, &game_path, &load_order)
+        .await
+        .map_err(|e| format!("Error loading data: {}", e))?;
+
+    *GAME_LOAD_ORDER.write().unwrap() = load_order;
+
+    Ok(items)
+}
+
+#[tauri::command]
+async fn reorder_categories(
+    app: tauri::AppHandle,
+    source_id: &str,
+    target_id: &str,
+) -> Result<Vec<String>, String> {
+    check_view_only()?;
+
+    // TODO: Move this to a sanitizer function.
+    let source_id = unescape(source_id);
+    let target_id = unescape(target_id);
+
+    let game_info = GAME_SELECTED.read().unwrap().clone();
+    let mut game_config = GAME_CONFIG.lock().unwrap().clone().unwrap();
+
+    let mut categories_order = game_config.categories_order().to_vec();
+    let source_index = categories_order
+        .iter()
+        .position(|id| id == &source_id)
+        .ok_or_else(|| format!("Source category '{}' not found", source_id))?;
+    let target_index = categories_order
+        .iter()
+        .position(|id| id == &target_id)
+        .ok_or_else(|| format!("Target category '{}' not found", target_id))?;
+
+    // Do nothing if they are the same category or already in the desired order.
+    if source_index == target_index {
+        return Ok(categories_order);
+    }
+
+    let source_category = categories_order.remove(source_index);
+    let new_target_index = if source_index < target_index {
+        target_index - 1
+    } else {
+        target_index
+    };
+
+    categories_order.insert(new_target_index, source_category);
+    game_config.set_categories_order(categories_order.to_vec());
+    game_config
+        .save_or_defer(&app, &game_info)
+        .map_err(|e| format!("Error al guardar la configuración: {}", e))?;
+
+    *GAME_CONFIG.lock().unwrap() = Some(game_config);
+
+    Ok(categories_order)
+}
+
+#[tauri::command]
+async fn create_category(app: tauri::AppHandle, category: &str) -> Result<Vec<String>, String> {
+    check_view_only()?;
+
+    let game_info = GAME_SELECTED.read().unwrap().clone();
+    let mut game_config = GAME_CONFIG.lock().unwrap().clone().unwrap();
+
+    // Create the category
+    game_config
+        .create_category(category)
+        .map_err(|e| format!("Error creating category: {}", e))?;
+
+    // Save the changes
+    game_config
+        .save_or_defer(&app, &game_info)
+        .map_err(|e| format!("Error saving configuration: {}", e))?;
+
+    let new_order = game_config.categories_order().to_vec();
+
+    // Update the game config in memory
+    *GAME_CONFIG.lock().unwrap() = Some(game_config);
+
+    Ok(new_order)
+}
+
+#[tauri::command]
+async fn rename_category(
+    app: tauri::AppHandle,
+    category: &str,
+    new_name: &str,
+) -> Result<(), String> {
+    check_view_only()?;
+
+    let game_info = GAME_SELECTED.read().unwrap().clone();
+    let mut game_config = GAME_CONFIG.lock().unwrap().clone().unwrap();
+
+    // Create the category
+    game_config
+        .rename_category(category, new_name)
+        .map_err(|e| format!("Error renaming category: {}", e))?;
+
+    // Save the changes
+    game_config
+        .save_or_defer(&app, &game_info)
+        .map_err(|e| format!("Error saving configuration: {}", e))?;
+
+    // Update the game config in memory
+    *GAME_CONFIG.lock().unwrap() = Some(game_config);
+
+    Ok(())
+}
+
+#[tauri::command]
+async fn remove_category(app: tauri::AppHandle, category: &str) -> Result<(), String> {
+    check_view_only()?;
+
+    let game_info = GAME_SELECTED.read().unwrap().clone();
+    let mut game_config = GAME_CONFIG.lock().unwrap().clone().unwrap();
+
+    // Create the category
+    game_config
+        .delete_category(category)
+        .map_err(|e| format!("Error deleting category: {}", e))?;
+
+    // Save the changes
+    game_config
+        .save_or_defer(&app, &game_info)
+        .map_err(|e| format!("Error saving configuration: {}", e))?;
+
+    // Update the game config in memory
+    *GAME_CONFIG.lock().unwrap() = Some(game_config);
+
+    Ok(())
+}
+
+#[tauri::command]
+async fn bulk_rename_local_mods(
+    app: tauri::AppHandle,
+    find: String,
+    replace: String,
+) -> Result<BulkRenameResult, String> {
+    check_view_only()?;
+
+    if find.is_empty() {
+        return Err("The text to find cannot be empty.".to_string());
+    }
+
+    let game_info = GAME_SELECTED.read().unwrap().clone();
+    let game_path = SETTINGS
+        .read()
+        .unwrap()
+        .game_path(&game_info)
+        .map_err(|e| format!("Error getting the game's path: {}", e))?;
+    let mut game_config = GAME_CONFIG
+        .lock()
+        .unwrap()
+        .clone()
+        .ok_or_else(|| "No game config loaded.".to_string())?;
+    let mut load_order = GAME_LOAD_ORDER.read().unwrap().clone();
+
+    let mut renames = game_config
+        .mods()
+        .values()
+        .filter(|modd| {
+            *modd.store_id() == StoreId::None
+                && *modd.pack_type() == PFHFileType::Mod
+                && !modd.paths().is_empty()
+                && modd.id().contains(&find)
+        })
+        .map(|modd| (modd.id().to_owned(), modd.id().replace(&find, &replace)))
+        .filter(|(id, new_id)| id != new_id)
+        .collect::<Vec<_>>();
+    renames.sort();
+
+    let mut result = BulkRenameResult::default();
+    let mut new_ids = vec![];
+    for (id, new_id) in renames {
+        let error = if !new_id.ends_with(".pack") || new_id == ".pack" {
+            Some("The new name must be a valid .pack file name.".to_string())
+        } else if new_id.contains(['/', '\\']) {
+            Some("The new name cannot contain path separators.".to_string())
+        } else if new_id == RESERVED_PACK_NAME || new_id == RESERVED_PACK_NAME_ALTERNATIVE {
+            Some("The new name is reserved for Runcher's own packs.".to_string())
+        } else if game_config.mods().contains_key(&new_id) || new_ids.contains(&new_id) {
+            Some(format!("A mod named {} already exists.", new_id))
+        } else {
+            game_config
+                .rename_mod(&id, &new_id)
+                .err()
+                .map(|e| e.to_string())
+        };
+
+        match error {
+            Some(error) => result.failed.push((id, error)),
+            None => {
+                load_order.rename_mod(&id, &new_id);
+                new_ids.push(new_id.clone());
+                result.renamed.push((id, new_id));
+            }
+        }
+    }
+
+    let _ = game_config
+        .update_mod_list(&app, &game_info, &game_path, &mut load_order, false)
+        .await
+        .map_err(|e| format!("Error loading data: {}", e))?;
+
+    game_config
+        .save(&app, &game_info)
+        .map_err(|e| format!("Error saving data: {}", e))?;
+
+    *GAME_LOAD_ORDER.write().unwrap() = load_order;
+    *GAME_CONFIG.lock().unwrap() = Some(game_config);
+
+    Ok(result)
+}
+
+/// Returns the mods that failed to be copied, and the updated pack list.
+#[tauri::command]
+async fn copy_mods_to_data(
+    app: tauri::AppHandle,
+    mod_ids: Vec<String>,
+) -> Result<(Vec<String>, Vec<ListItem>), String> {
+    check_view_only()?;
+
+    let game_info = GAME_SELECTED.read().unwrap().clone();
+    let game_path = SETTINGS
+        .read()
+        .unwrap()
+        .game_path(&game_info)
+        .map_err(|e| format!("Error getting the game's path: {}", e))?;
+    let mut game_config = GAME_CONFIG
+        .lock()
+        .unwrap()
+        .clone()
+        .ok_or_else(|| "No game config loaded.".to_string())?;
+    let mut load_order = GAME_LOAD_ORDER.read().unwrap().clone();
+
+    let mods_failed = copy_to_data(&app, &game_info, &game_config, &mod_ids)
+        .map_err(|e| format!("Error copying mods to /data: {}", e))?;
+
+    // Rescan the mods so the new /data paths get registered.
+    let _ = game_config
+        .update_mod_list(&app, &game_info, &game_path, &mut load_order, false)
+        .await
+        .map_err(|e| format!("Error loading data: {}", e))?;
+    let items = load_packs(&app, &game_config, &game_info, &game_path, &load_order)
+        .await
+        .map_err(|e| format!("Error loading data: {}", e))?;
+
+    game_config
+        .save(&app, &game_info)
+        .map_err(|e| format!("Error saving data: {}", e))?;
 
-                        /*
-                        if modd.can_be_toggled(game, &game_data_path) {
-                            item_mod_name.set_checkable(true);
+    *GAME_LOAD_ORDER.write().unwrap() = load_order;
+    *GAME_CONFIG.lock().unwrap() = Some(game_config);
 
-                            if modd.enabled(game, &game_data_path) {
-                                item_mod_name.set_check_state(CheckState::Checked);
-                            }
-                        }
+    Ok((mods_failed, items))
+}
 
-                        // This is for movie mods in /data.
-                        else {
-                            item_mod_name.set_checkable(true);
-                            item_mod_name.set_check_state(CheckState::Checked);
+/// Copies mods from the content folder to the secondary folder. Returns the mods that failed to be copied, and the updated pack list.
+#[tauri::command]
+async fn copy_mods_to_secondary(
+    app: tauri::AppHandle,
+    mod_ids: Vec<String>,
+) -> Result<(Vec<String>, Vec<ListItem>), String> {
+    relocate_mods_to_secondary(&app, &mod_ids, false).await
+}
 
-                            let mut flags = item_mod_name.flags().to_int();
-                            flags &= !ItemFlag::ItemIsUserCheckable.to_int();
-                            item_mod_name.set_flags(QFlags::from(flags));
-                        }*/
+/// Moves mods from /data to the secondary folder. Returns the mods that failed to be moved, and the updated pack list.
+#[tauri::command]
+async fn move_mods_to_secondary(
+    app: tauri::AppHandle,
+    mod_ids: Vec<String>,
+) -> Result<(Vec<String>, Vec<ListItem>), String> {
+    relocate_mods_to_secondary(&app, &mod_ids, true).await
+}
 
-                        item.is_checked = modd.enabled(game, &game_data_path);
+async fn relocate_mods_to_secondary(
+    app: &tauri::AppHandle,
+    mod_ids: &[String],
+    remove_original: bool,
+) -> Result<(Vec<String>, Vec<ListItem>), String> {
+    check_view_only()?;
 
-                        cat_item.children.push(item);
-                    }
-                }
-            }
-        }
-        categories.push(cat_item);
+    let game_info = GAME_SELECTED.read().unwrap().clone();
+    if !game_supports_secondary_folder(&game_info) {
+        return Err(format!(
+            "{} doesn't support the secondary folder.",
+            game_info.display_name()
+        ));
     }
 
-    Ok(categories)
-}
+    let game_path = SETTINGS
+        .read()
+        .unwrap()
+        .game_path(&game_info)
+        .map_err(|e| format!("Error getting the game's path: {}", e))?;
+    let mut game_config = GAME_CONFIG
+        .lock()
+        .unwrap()
+        .clone()
+        .ok_or_else(|| "No game config loaded.".to_string())?;
+    let mut load_order = GAME_LOAD_ORDER.read().unwrap().clone();
 
-async fn load_packs(
-    app: &tauri::AppHandle,
-    game_config: &GameConfig,
-    game_info: &GameInfo,
-    game_path: &Path,
-    load_order: &LoadOrder,
-) -> anyhow::Result<Vec<ListItem>> {
-    use crate::mod_manager::secondary_mods_path;
-    use rpfm_lib::files::pack::Pack;
+    let mods_failed = if remove_original {
+        move_to_secondary(app, &game_info, &game_config, mod_ids)
+            .map_err(|e| format!("Error moving mods to the secondary folder: {}", e))?
+    } else {
+        copy_to_secondary(app, &game_info, &game_config, mod_ids)
+            .map_err(|e| format!("Error copying mods to the secondary folder: {}", e))?
+    };
 
-    let mut items = vec![];
+    // Rescan the mods so the new secondary paths get registered.
+    game_config
+        .update_mod_list(app, &game_info, &game_path, &mut load_order, false)
+        .await
+        .map_err(|e| format!("Error loading data: {}", e))?;
+    let items = load_packs(app, &game_config, &game_info, &game_path, &load_order)
+        .await
+        .map_err(|e| format!("Error loading data: {}", e))?;
 
-    let secondary_mods_path = secondary_mods_path(app, game_config.game_key()).unwrap_or_default();
-    if !game_path.to_string_lossy().is_empty() {
-        if let Ok(game_data_folder) = game_info.data_path(game_path) {
-            let game_data_folder = std::fs::canonicalize(game_data_folder.clone())
-                .unwrap_or_else(|_| game_data_folder.clone());
+    game_config
+        .save_or_defer(app, &game_info)
+        .map_err(|e| format!("Error saving data: {}", e))?;
 
-            // Chain so movie packs are always last.
-            let mods = load_order.mods().iter().chain(load_order.movies().iter());
-            for (index, mod_id) in mods.enumerate() {
-                if let Some(modd) = game_config.mods().get(mod_id) {
-                    let pack_name = modd.paths()[0]
-                        .file_name()
-                        .unwrap()
-                        .to_string_lossy()
-                        .as_ref()
-                        .to_owned();
+    *GAME_LOAD_ORDER.write().unwrap() = load_order;
+    *GAME_CONFIG.lock().unwrap() = Some(game_config);
 
-                    // This is needed to avoid errors with map packs before we process them.
-                    //
-                    // In practice if a bin pack loads here, there's a bug elsewhere.
-                    if pack_name.ends_with(".pack") {
-                        let pack = Pack::read_and_merge(
-                            &[modd.paths()[0].to_path_buf()],
-                            true,
-                            false,
-                            false,
-                            false,
-                        )?;
+    Ok((mods_failed, items))
+}
 
-                        let mut item = ListItem::default();
-                        item.id = mod_id.to_string();
-                        item.pack = pack_name;
-                        item.r#type = modd.pack_type().to_string();
-                        item.order = index as i32;
-                        item.location = if modd.paths()[0].starts_with(&game_data_folder) {
-                            "Data".to_string()
-                        } else if secondary_mods_path.is_dir()
-                            && modd.paths()[0].starts_with(&secondary_mods_path)
-                        {
-                            format!("Secondary ({})", match modd.store_id() {
-                                StoreId::None => "Local",
-                                StoreId::Steam(ref id) => id,
-                                StoreId::Epic(ref id) => id,
-                                StoreId::Nexus(ref id) => id,
-                                StoreId::ModDB(ref id) => id,
-                                StoreId::LoversLab(ref id) => id,
-                                StoreId::Github(ref id) => id,
-                            })
-                        } else if let StoreId::None = modd.store_id() {
-                            "Where the fuck is this pack?".to_string()
-                        } else {
-                            format!("Content ({})", match modd.store_id() {
-                                StoreId::None => "Local",
-                                StoreId::Steam(ref id) => id,
-                                StoreId::Epic(ref id) => id,
-                                StoreId::Nexus(ref id) => id,
-                                StoreId::ModDB(ref id) => id,
-                                StoreId::LoversLab(ref id) => id,
-                                StoreId::Github(ref id) => id,
-                            })
-                        };
+/// Saves the current load order and enabled mods as a profile, replacing any profile with the same name.
+#[tauri::command]
+fn create_profile(app: tauri::AppHandle, name: &str) -> Result<Vec<String>, String> {
+    check_view_only()?;
 
-                        //item.store_id = modd.store_id().clone();
-                        items.push(item);
-                    } else {
-                        // TODO: fix this case in shogun 2. Also seen up to warhammer 1....
-                        //error!("Error loading Pack to UI: {}", modd.paths()[0].to_string_lossy())
-                    }
-                }
-            }
-        }
-    }
+    let game_info = GAME_SELECTED.read().unwrap().clone();
+    let load_order = GAME_LOAD_ORDER.read().unwrap().clone();
 
-    Ok(items)
+    let mut profile = Profile::new(&game_info, name, &load_order)
+        .map_err(|e| format!("Error creating profile: {}", e))?;
+    let name = profile.id().to_owned();
+    profile
+        .save(&app, &game_info, &name)
+        .map_err(|e| format!("Error saving profile: {}", e))?;
+
+    let mut profiles = GAME_PROFILES.write().unwrap();
+    profiles.insert(name, profile);
+
+    let mut names = profiles.keys().cloned().collect::<Vec<_>>();
+    names.sort();
+    Ok(names)
 }
 
+/// Restores the load order and enabled mods of a profile. Mods no longer installed are ignored.
 #[tauri::command]
-async fn move_pack_in_load_order_in_direction(
-    app: tauri::AppHandle,
-    mod_id: &str,
-    direction: LoadOrderDirectionMove,
-) -> Result<Vec<ListItem>, String> {
+async fn apply_profile(app: tauri::AppHandle, name: &str) -> Result<Vec<ListItem>, String> {
+    check_view_only()?;
+
     let game_info = GAME_SELECTED.read().unwrap().clone();
-    let game_path = SETTINGS.read().unwrap().game_path(&game_info).unwrap();
-    let game_config = GAME_CONFIG.lock().unwrap().clone().unwrap();
-    let mut load_order = GAME_LOAD_ORDER.read().unwrap().clone();
-    let mod_id = unescape(mod_id);
+    let game_path = SETTINGS
+        .read()
+        .unwrap()
+        .game_path(&game_info)
+        .map_err(|e| format!("Error getting the game's path: {}", e))?;
+    let game_data_path = game_info
+        .data_path(&game_path)
+        .map_err(|e| format!("Error getting the game's data path: {}", e))?;
+    let mut game_config = GAME_CONFIG
+        .lock()
+        .unwrap()
+        .clone()
+        .ok_or_else(|| "No game config loaded.".to_string())?;
 
-    load_order.move_mod_in_direction(&mod_id, direction);
+    let mut load_order = GAME_PROFILES
+        .read()
+        .unwrap()
+        .get(name)
+        .map(|profile| profile.load_order().clone())
+        .ok_or_else(|| format!("Profile {} not found.", name))?;
+
+    let is_installed = |mod_id: &String| {
+        game_config
+            .mods()
+            .get(mod_id)
+            .is_some_and(|modd| !modd.paths().is_empty())
+    };
+    load_order.mods_mut().retain(is_installed);
+    load_order.movies_mut().retain(is_installed);
+
+    // Only the mods in the profile are enabled.
+    let enabled = load_order
+        .mods()
+        .iter()
+        .chain(load_order.movies().iter())
+        .cloned()
+        .collect::<HashSet<_>>();
+    for modd in game_config.mods_mut().values_mut() {
+        if modd.can_be_toggled(&game_info, &game_data_path) {
+            let is_enabled = enabled.contains(modd.id());
+            modd.set_enabled(is_enabled);
+        }
+    }
+
+    game_config
+        .update_mod_list(&app, &game_info, &game_path, &mut load_order, true)
+        .await
+        .map_err(|e| format!("Error loading data: {}", e))?;
     let items = load_packs(&app, &game_config, &game_info, &game_path, &load_order)
         .await
         .map_err(|e| format!("Error loading data: {}", e))?;
 
+    game_config
+        .save_or_defer(&app, &game_info)
+        .map_err(|e| format!("Error saving data: {}", e))?;
+    load_order
+        .save_or_defer(&app, &game_info)
+        .map_err(|e| format!("Error saving data: {}", e))?;
+
     *GAME_LOAD_ORDER.write().unwrap() = load_order;
+    *GAME_CONFIG.lock().unwrap() = Some(game_config);
 
     Ok(items)
 }
 
 #[tauri::command]
-async fn move_pack_in_load_order(
-    app: tauri::AppHandle,
-    source_id: &str,
-    target_id: &str,
-) -> Result<Vec<ListItem>, String> {
+fn delete_profile(app: tauri::AppHandle, name: &str) -> Result<Vec<String>, String> {
+    check_view_only()?;
+
     let game_info = GAME_SELECTED.read().unwrap().clone();
-    let game_path = SETTINGS.read().unwrap().game_path(&game_info).unwrap();
-    let game_config = GAME_CONFIG.lock().unwrap().clone().unwrap();
-    let mut load_order = GAME_LOAD_ORDER.read().unwrap().clone();
-    let source_id = unescape(source_id);
-    let target_id = unescape(target_id);
+    let mut profiles = GAME_PROFILES.write().unwrap();
+    let profile = profiles
+        .get(name)
+        .ok_or_else(|| format!("Profile {} not found.", name))?;
+
+    profile
+        .delete(&app, &game_info)
+        .map_err(|e| format!("Error deleting profile: {}", e))?;
+    profiles.remove(name);
+
+    let mut names = profiles.keys().cloned().collect::<Vec<_>>();
+    names.sort();
+    Ok(names)
+}
 
-    load_order.move_mod_above_another(&source_id, &target_id);
-    let items = load_packs(&app, &game_config, &game_info, &game_path, &load_order)
+#[tauri::command]
+fn get_pack_type_changes() -> Result<Vec<PackTypeChange>, String> {
+    let game_config = GAME_CONFIG
+        .lock()
+        .unwrap()
+        .clone()
+        .ok_or_else(|| "No game config loaded.".to_string())?;
+
+    Ok(game_config
+        .pack_type_changes()
+        .iter()
+        .map(|(id, (old_type, new_type))| PackTypeChange {
+            id: id.to_owned(),
+            old_type: old_type.to_string(),
+            new_type: new_type.to_string(),
+        })
+        .collect())
+}
+
+#[tauri::command]
+fn find_uncategorized_mods() -> Result<Vec<String>, String> {
+    GAME_CONFIG
+        .lock()
+        .unwrap()
+        .as_ref()
+        .map(|game_config| game_config.uncategorized_mods())
+        .ok_or_else(|| "No game config loaded.".to_string())
+}
+
+#[tauri::command]
+async fn fix_uncategorized(app: tauri::AppHandle) -> Result<Vec<TreeCategory>, String> {
+    check_view_only()?;
+
+    let game_info = GAME_SELECTED.read().unwrap().clone();
+    let mut game_config = GAME_CONFIG
+        .lock()
+        .unwrap()
+        .clone()
+        .ok_or_else(|| "No game config loaded.".to_string())?;
+
+    game_config.fix_uncategorized_mods();
+
+    game_config
+        .save_or_defer(&app, &game_info)
+        .map_err(|e| format!("Error saving configuration: {}", e))?;
+
+    let categories = load_mods(&app, &game_info, &game_config)
         .await
         .map_err(|e| format!("Error loading data: {}", e))?;
 
-    *GAME_LOAD_ORDER.write().unwrap() = load_order;
+    *GAME_CONFIG.lock().unwrap() = Some(game_config);
 
-    Ok(items)
+    Ok(categories)
 }
 
 #[tauri::command]
-async fn reorder_categories(
-    app: tauri::AppHandle,
-    source_id: &str,
-    target_id: &str,
-) -> Result<Vec<String>, String> {
-    // TODO: Move this to a sanitizer function.
-    let source_id = unescape(source_id);
-    let target_id = unescape(target_id);
+async fn remove_empty_categories(app: tauri::AppHandle) -> Result<Vec<TreeCategory>, String> {
+    check_view_only()?;
 
     let game_info = GAME_SELECTED.read().unwrap().clone();
-    let mut game_config = GAME_CONFIG.lock().unwrap().clone().unwrap();
+    let mut game_config = GAME_CONFIG
+        .lock()
+        .unwrap()
+        .clone()
+        .ok_or_else(|| "No game config loaded.".to_string())?;
 
-    let mut categories_order = game_config.categories_order().to_vec();
-    let source_index = categories_order
-        .iter()
-        .position(|id| id == &source_id)
-        .ok_or_else(|| format!("Source category '{}' not found", source_id))?;
-    let target_index = categories_order
-        .iter()
-        .position(|id| id == &target_id)
-        .ok_or_else(|| format!("Target category '{}' not found", target_id))?;
+    game_config.remove_empty_categories();
 
-    // Do nothing if they are the same category or already in the desired order.
-    if source_index == target_index {
-        return Ok(categories_order);
-    }
+    game_config
+        .save_or_defer(&app, &game_info)
+        .map_err(|e| format!("Error saving configuration: {}", e))?;
 
-    let source_category = categories_order.remove(source_index);
-    let new_target_index = if source_index < target_index {
-        target_index - 1
-    } else {
-        target_index
+    let categories = load_mods(&app, &game_info, &game_config)
+        .await
+        .map_err(|e| format!("Error loading data: {}", e))?;
+
+    *GAME_CONFIG.lock().unwrap() = Some(game_config);
+
+    Ok(categories)
+}
+
+#[tauri::command]
+async fn compare_with_subscriptions(app: tauri::AppHandle) -> Result<SubscriptionsReport, String> {
+    let game_info = GAME_SELECTED.read().unwrap().clone();
+    let game_path = SETTINGS
+        .read()
+        .unwrap()
+        .game_path(&game_info)
+        .map_err(|e| format!("Error getting the game's path: {}", e))?;
+    let game_config = GAME_CONFIG
+        .lock()
+        .unwrap()
+        .clone()
+        .ok_or_else(|| "No game config loaded.".to_string())?;
+
+    let subscribed = {
+        let app = app.clone();
+        let game_info = game_info.clone();
+        tauri::async_runtime::spawn_blocking(move || {
+            Integrations::subscribed_mod_ids(&app, &game_info)
+        })
+        .await
+        .map_err(|e| format!("Error getting the subscribed mods: {}", e))?
+        .map_err(|e| format!("Error getting the subscribed mods: {}", e))?
     };
 
-    categories_order.insert(new_target_index, source_category);
-    game_config.set_categories_order(categories_order.to_vec());
-    game_config
-        .save(&app, &game_info)
-        .map_err(|e| format!("Error al guardar la configuración: {}", e))?;
+    // Only mods found in the content folder count as downloaded from the workshop.
+    let content_path =
+        path_to_absolute_string(&game_info.content_path(&game_path).unwrap_or_default());
+    let mut installed = HashMap::new();
+    if !content_path.is_empty() {
+        for modd in game_config.mods().values() {
+            if let StoreId::Steam(id) = modd.store_id() {
+                if modd
+                    .paths()
+                    .iter()
+                    .any(|path| path_to_absolute_string(path).starts_with(&content_path))
+                {
+                    installed.insert(id.to_owned(), modd.id().to_owned());
+                }
+            }
+        }
+    }
+
+    let mut report = SubscriptionsReport::default();
+    for id in &subscribed {
+        if !installed.contains_key(id) {
+            report.subscribed_but_missing.push(id.to_owned());
+        }
+    }
 
-    *GAME_CONFIG.lock().unwrap() = Some(game_config);
+    for (id, mod_id) in &installed {
+        if !subscribed.contains(id) {
+            report
+                .present_but_not_subscribed
+                .push((id.to_owned(), mod_id.to_owned()));
+        }
+    }
 
-    Ok(categories_order)
+    report.subscribed_but_missing.sort();
+    report.present_but_not_subscribed.sort();
+
+    Ok(report)
 }
 
 #[tauri::command]
-async fn create_category(app: tauri::AppHandle, category: &str) -> Result<Vec<String>, String> {
+fn get_workshop_summary() -> Result<WorkshopSummary, String> {
     let game_info = GAME_SELECTED.read().unwrap().clone();
-    let mut game_config = GAME_CONFIG.lock().unwrap().clone().unwrap();
-
-    // Create the category
-    game_config
-        .create_category(category)
-        .map_err(|e| format!("Error creating category: {}", e))?;
-
-    // Save the changes
-    game_config
-        .save(&app, &game_info)
-        .map_err(|e| format!("Error saving configuration: {}", e))?;
+    let game_path = SETTINGS
+        .read()
+        .unwrap()
+        .game_path(&game_info)
+        .map_err(|e| format!("Error getting the game's path: {}", e))?;
+    let game_config = GAME_CONFIG.lock().unwrap();
+    let game_config = game_config
+        .as_ref()
+        .ok_or_else(|| "No game config loaded.".to_string())?;
+
+    let mut summary = WorkshopSummary::default();
+    let content_path =
+        path_to_absolute_string(&game_info.content_path(&game_path).unwrap_or_default());
+    if content_path.is_empty() {
+        return Ok(summary);
+    }
 
-    let new_order = game_config.categories_order().to_vec();
+    for modd in game_config.mods().values() {
+        if let StoreId::Steam(_) = modd.store_id() {
+            if modd
+                .paths()
+                .iter()
+                .any(|path| path_to_absolute_string(path).starts_with(&content_path))
+            {
+                summary.installed += 1;
 
-    // Update the game config in memory
-    *GAME_CONFIG.lock().unwrap() = Some(game_config);
+                // Mods whose metadata couldn't be resolved keep their pack name as name.
+                if modd.name() != modd.id() {
+                    summary.resolved += 1;
+                } else {
+                    summary.unresolved += 1;
+                }
+            }
+        }
+    }
 
-    Ok(new_order)
+    Ok(summary)
 }
 
+/// Tells Steam to download the subscribed mods, or only the provided ones, then rescans the mods so the new packs show up.
 #[tauri::command]
-async fn rename_category(
+async fn download_subscribed_mods(
     app: tauri::AppHandle,
-    category: &str,
-    new_name: &str,
-) -> Result<(), String> {
+    published_file_ids: Option<Vec<String>>,
+) -> Result<(Vec<TreeCategory>, Vec<ListItem>), String> {
+    check_view_only()?;
+
     let game_info = GAME_SELECTED.read().unwrap().clone();
-    let mut game_config = GAME_CONFIG.lock().unwrap().clone().unwrap();
+    let game_path = SETTINGS
+        .read()
+        .unwrap()
+        .game_path(&game_info)
+        .map_err(|e| format!("Error getting the game's path: {}", e))?;
+
+    let integrations = INTEGRATIONS.lock().unwrap().clone();
+    let tx_recv = integrations
+        .download_subscribed_mods(&app, &game_info, &published_file_ids)
+        .await;
+    Integrations::recv_download_subscribed_mods(tx_recv)
+        .await
+        .map_err(|e| format!("Error downloading the subscribed mods: {}", e))?;
+
+    let mut game_config = GAME_CONFIG
+        .lock()
+        .unwrap()
+        .clone()
+        .ok_or_else(|| "No game config loaded.".to_string())?;
+    let mut load_order = GAME_LOAD_ORDER.read().unwrap().clone();
 
-    // Create the category
     game_config
-        .rename_category(category, new_name)
-        .map_err(|e| format!("Error renaming category: {}", e))?;
+        .update_mod_list(&app, &game_info, &game_path, &mut load_order, false)
+        .await
+        .map_err(|e| format!("Error loading data: {}", e))?;
+    let mods = load_mods(&app, &game_info, &game_config)
+        .await
+        .map_err(|e| format!("Error loading data: {}", e))?;
+    let items = load_packs(&app, &game_config, &game_info, &game_path, &load_order)
+        .await
+        .map_err(|e| format!("Error loading data: {}", e))?;
 
-    // Save the changes
     game_config
-        .save(&app, &game_info)
-        .map_err(|e| format!("Error saving configuration: {}", e))?;
+        .save_or_defer(&app, &game_info)
+        .map_err(|e| format!("Error saving data: {}", e))?;
 
-    // Update the game config in memory
+    *GAME_LOAD_ORDER.write().unwrap() = load_order;
     *GAME_CONFIG.lock().unwrap() = Some(game_config);
 
-    Ok(())
+    Ok((mods, items))
 }
 
 #[tauri::command]
-async fn remove_category(app: tauri::AppHandle, category: &str) -> Result<(), String> {
+async fn redownload_workshop_mod(
+    app: tauri::AppHandle,
+    mod_id: &str,
+) -> Result<Vec<ListItem>, String> {
+    check_view_only()?;
+
+    let mod_id = unescape(mod_id);
     let game_info = GAME_SELECTED.read().unwrap().clone();
-    let mut game_config = GAME_CONFIG.lock().unwrap().clone().unwrap();
+    let game_path = SETTINGS
+        .read()
+        .unwrap()
+        .game_path(&game_info)
+        .map_err(|e| format!("Error getting the game's path: {}", e))?;
+
+    let published_file_id = match GAME_CONFIG
+        .lock()
+        .unwrap()
+        .as_ref()
+        .ok_or_else(|| "No game config loaded.".to_string())?
+        .mods()
+        .get(&mod_id)
+        .map(|modd| modd.store_id().clone())
+    {
+        Some(StoreId::Steam(id)) => id,
+        Some(_) => return Err(format!("Mod {} is not a workshop mod.", mod_id)),
+        None => return Err(format!("Mod {} not found.", mod_id)),
+    };
+
+    {
+        let app = app.clone();
+        let game_info = game_info.clone();
+        tauri::async_runtime::spawn_blocking(move || {
+            Integrations::redownload_mods(&app, &game_info, &[published_file_id])
+        })
+        .await
+        .map_err(|e| format!("Error downloading the mod: {}", e))?
+        .map_err(|e| format!("Error downloading the mod: {}", e))?;
+    }
+
+    // Reload after the download, as the files of the mod may have changed.
+    let mut game_config = GAME_CONFIG
+        .lock()
+        .unwrap()
+        .clone()
+        .ok_or_else(|| "No game config loaded.".to_string())?;
+    let mut load_order = GAME_LOAD_ORDER.read().unwrap().clone();
 
-    // Create the category
     game_config
-        .delete_category(category)
-        .map_err(|e| format!("Error deleting category: {}", e))?;
+        .update_mod_list(&app, &game_info, &game_path, &mut load_order, false)
+        .await
+        .map_err(|e| format!("Error loading data: {}", e))?;
+    let items = load_packs(&app, &game_config, &game_info, &game_path, &load_order)
+        .await
+        .map_err(|e| format!("Error loading data: {}", e))?;
 
-    // Save the changes
     game_config
-        .save(&app, &game_info)
-        .map_err(|e| format!("Error saving configuration: {}", e))?;
+        .save_or_defer(&app, &game_info)
+        .map_err(|e| format!("Error saving data: {}", e))?;
 
-    // Update the game config in memory
+    *GAME_LOAD_ORDER.write().unwrap() = load_order;
     *GAME_CONFIG.lock().unwrap() = Some(game_config);
 
-    Ok(())
+    Ok(items)
 }
 
 #[tauri::command]
@@ -1194,6 +4438,32 @@ fn send_progress_event(app: &tauri::AppHandle, progress: i32, total: i32) {
     );
 }
 
+/// Util function to escape backend ids the same way the UI does, with `CSS.escape`.
+fn escape(id: &str) -> String {
+    let chars = id.chars().collect::<Vec<_>>();
+    let mut escaped = String::with_capacity(id.len());
+    for (index, character) in chars.iter().enumerate() {
+        match character {
+            '\0' => escaped.push('\u{FFFD}'),
+            '\u{1}'..='\u{1F}' | '\u{7F}' => {
+                escaped.push_str(&format!("\\{:x} ", *character as u32))
+            }
+            '0'..='9' if index == 0 || (index == 1 && chars[0] == '-') => {
+                escaped.push_str(&format!("\\{:x} ", *character as u32))
+            }
+            '-' if index == 0 && chars.len() == 1 => escaped.push_str("\\-"),
+            '-' | '_' | '0'..='9' | 'a'..='z' | 'A'..='Z' => escaped.push(*character),
+            _ if *character >= '\u{80}' => escaped.push(*character),
+            _ => {
+                escaped.push('\\');
+                escaped.push(*character);
+            }
+        }
+    }
+
+    escaped
+}
+
 /// Util function to de-escape ui-coming ids so they can be used in the backend.
 ///
 /// This is needed because UI-coming IDs have some rules that the backend doesn't, like:
@@ -1203,6 +4473,404 @@ fn unescape(id: &str) -> String {
     id.replace("\\", "").replace("mod:", "").replace("cat:", "")
 }
 
+/// Masks the disabled movies, generates the temp pack and writes the load order file, so the game is ready to be launched.
+///
+/// Returns the path of the load order file, and a report of what was written.
+fn prepare_launch_files(
+    app: &tauri::AppHandle,
+    launch_options: &[LaunchOption],
+) -> Result<(PathBuf, LaunchPreparation), String> {
+    let mut folder_list = String::new();
+    let mut pack_list = String::new();
+    let mut preparation = LaunchPreparation::default();
+
+    // If autosave is disabled, make sure the game is launched with what's on disk.
+    save_unsaved_changes(app).map_err(|e| format!("Error saving data: {}", e))?;
+
+    let game = GAME_SELECTED.read().unwrap().clone();
+    let game_path = SETTINGS
+        .read()
+        .unwrap()
+        .game_path(&game)
+        .map_err(|e| format!("Error getting the game's path: {}", e))?;
+    let data_path = game
+        .data_path(&game_path)
+        .map_err(|e| format!("Error getting the game's data path: {}", e))?;
+    let game_config = GAME_CONFIG
+        .lock()
+        .unwrap()
+        .clone()
+        .ok_or_else(|| "No game config loaded.".to_string())?;
+    let load_order = GAME_LOAD_ORDER.read().unwrap().clone();
+
+    // On fresh installs some folders are missing until the game is launched once, and we need them to build the load order.
+    ensure_game_folders(&game, &game_path)
+        .map_err(|e| format!("Error preparing the game's folders: {}", e))?;
+
+    load_order
+        .build_load_order_string(
+            app,
+            &game_config,
+            &game,
+            &data_path,
+            &mut pack_list,
+            &mut folder_list,
+        )
+        .map_err(|e| format!("Error building the load order: {}", e))?;
+
+    for mod_id in load_order.mods().iter().chain(load_order.movies().iter()) {
+        if game_config
+            .mods()
+            .get(mod_id)
+            .is_none_or(|modd| modd.paths().is_empty())
+        {
+            preparation.warnings.push(format!(
+                "Mod {} is in the load order, but it's not installed.",
+                mod_id
+            ));
+        }
+    }
+
+    // If our folder list contains the secondary folder, we need to make sure we create the masks folder in it,
+    // and mask in there all non-enabled movie files. Note that we only use this in games older than warhammer. Newer games use the exclude_pack_file command.
+    if game_uses_movie_masking(&game) {
+        let secondary_mods_path =
+            secondary_mods_path(app, game.key()).unwrap_or_else(|_| PathBuf::new());
+        let secondary_mods_path_str = load_order_path_string(&secondary_mods_path);
+
+        if secondary_mods_path.is_dir() && folder_list.contains(&secondary_mods_path_str) {
+            generate_movie_masks(&game, &game_config, &data_path, &secondary_mods_path)
+                .map_err(|e| format!("Error masking the disabled movie packs: {}", e))?;
+
+            preparation.written_paths.push(path_to_absolute_string(
+                &secondary_mods_path.join(SECONDARY_FOLDER_NAME),
+            ));
+        }
+    }
+
+    let file_path = LoadOrder::path_as_load_order_file(&game, &game_path)
+        .map_err(|e| format!("Error getting the load order file path: {}", e))?;
+
+    // Setup the launch options stuff. This may add a line to the folder list, so we need to resave the load order file after this.
+    let folder_list_pre = folder_list.to_owned();
+    LoadOrder::save_as_load_order_file(&file_path, &game, &folder_list, &pack_list)
+        .map_err(|e| format!("Error saving the load order file: {}", e))?;
+    let mut launch_options_data = LAUNCH_OPTIONS.write().unwrap();
+    let temp_pack_path = launch_options_data
+        .prepare_launch_options(app, launch_options, &game, &data_path, &mut folder_list)
+        .map_err(|e| format!("Error preparing launch options: {}", e))?;
+
+    if let Some(temp_pack_path) = temp_pack_path {
+        preparation
+            .written_paths
+            .push(path_to_absolute_string(&temp_pack_path));
+    }
+
+    for error in launch_options_data.script_parse_errors() {
+        preparation.warnings.push(format!(
+            "Script {} failed to load and it's not available.",
+            error.path().to_string_lossy()
+        ));
+    }
+    drop(launch_options_data);
+
+    if folder_list != folder_list_pre {
+        LoadOrder::save_as_load_order_file(&file_path, &game, &folder_list, &pack_list)
+            .map_err(|e| format!("Error saving the load order file: {}", e))?;
+    }
+
+    // Better to fail here than to launch the game with a truncated mod list.
+    LoadOrder::verify_load_order_file(&file_path, &game, &folder_list, &pack_list)
+        .map_err(|e| format!("Error verifying the load order file: {}", e))?;
+
+    preparation
+        .written_paths
+        .push(path_to_absolute_string(&file_path));
+
+    Ok((file_path, preparation))
+}
+
+/// Formats a conflict report as plain text, for sharing it where json is not readable.
+fn conflict_report_text(report: &ConflictReport) -> String {
+    let mut text = format!("Conflict report for {}\n\nLoad order:\n", report.game);
+    for (index, mod_id) in report.mods.iter().chain(report.movies.iter()).enumerate() {
+        text.push_str(&format!("{:>4}. {}\n", index + 1, mod_id));
+    }
+
+    for (title, conflicts) in [
+        ("File conflicts", &report.file_conflicts),
+        ("DB table conflicts", &report.table_conflicts),
+    ] {
+        text.push_str(&format!("\n{} ({}):\n", title, conflicts.len()));
+        for conflict in conflicts {
+            text.push_str(&format!(
+                "- {}: {} (winner: {})\n",
+                conflict.path,
+                conflict.mods.join(", "),
+                conflict.winner
+            ));
+        }
+    }
+
+    text
+}
+
+/// Checks an imported load order against the installed mods of the current game.
+fn imported_load_order_report(
+    mode: &ImportedLoadOrderMode,
+) -> anyhow::Result<ImportedLoadOrderReport> {
+    let game_config = GAME_CONFIG
+        .lock()
+        .unwrap()
+        .clone()
+        .ok_or_else(|| anyhow!("No game config loaded."))?;
+
+    let hashes = mode.mod_hashes()?;
+    let mut report = ImportedLoadOrderReport::default();
+    for mod_id in mode.mod_ids()? {
+        match game_config.mods().get(&mod_id) {
+            Some(modd) if !modd.paths().is_empty() => {
+                // A different hash means the user has a different version of the mod.
+                if let Some(hash) = hashes.get(&mod_id) {
+                    if ShareableMod::from(modd).hash() != hash {
+                        report.mismatched.push(mod_id.to_owned());
+                    }
+                }
+
+                report.mods.push(mod_id);
+            }
+            _ => report.missing.push(mod_id),
+        }
+    }
+
+    Ok(report)
+}
+
+/// Returns the paths of a mod in priority order, along with where each one is and when it was last modified.
+fn mod_path_details(
+    modd: &Mod,
+    data_path: &str,
+    secondary_path: &str,
+    content_path: &str,
+) -> Vec<ModPathDetail> {
+    modd.paths()
+        .iter()
+        .enumerate()
+        .map(|(index, path)| ModPathDetail {
+            path: path_to_absolute_string(path),
+            location: Mod::path_location(path, data_path, secondary_path, content_path),
+            active: index == 0,
+            modified: path
+                .metadata()
+                .and_then(|metadata| metadata.modified())
+                .ok()
+                .and_then(|date| date.duration_since(UNIX_EPOCH).ok())
+                .map(|date| date.as_secs())
+                .unwrap_or_default(),
+        })
+        .collect()
+}
+
+/// Fails if the view-only mode is active. Call it before changing anything in mutating commands.
+fn check_view_only() -> Result<(), String> {
+    if VIEW_ONLY.load(Ordering::SeqCst) {
+        Err("View-only mode is active.".to_string())
+    } else {
+        Ok(())
+    }
+}
+
+/// Saves the game config and load order of the selected game, if they have changes not yet saved to disk.
+fn save_unsaved_changes(app: &tauri::AppHandle) -> anyhow::Result<()> {
+    if UNSAVED_CHANGES.load(Ordering::SeqCst) {
+        let game = GAME_SELECTED.read().unwrap().clone();
+        if let Some(game_config) = GAME_CONFIG.lock().unwrap().as_mut() {
+            game_config.save(app, &game)?;
+        }
+
+        GAME_LOAD_ORDER.write().unwrap().save(app, &game)?;
+        UNSAVED_CHANGES.store(false, Ordering::SeqCst);
+    }
+
+    Ok(())
+}
+
+/// Saves the game config, load order, profiles and settings to disk. Failures don't stop the rest from being saved.
+fn flush_all_state(app: &tauri::AppHandle) -> FlushStateResult {
+    let mut result = FlushStateResult::default();
+    let game = GAME_SELECTED.read().unwrap().clone();
+
+    // Without a game config nothing was loaded for this game, so saving the default load order would overwrite the real one.
+    let game_loaded = match GAME_CONFIG.lock().unwrap().as_mut() {
+        Some(game_config) => {
+            match game_config.save(app, &game) {
+                Ok(_) => result.game_config = true,
+                Err(error) => result
+                    .errors
+                    .push(format!("Error saving the game config: {}", error)),
+            }
+            true
+        }
+
+        // There's nothing in memory to lose, so there's nothing to save either.
+        None => {
+            result.game_config = true;
+            result.load_order = true;
+            result.profiles = true;
+            false
+        }
+    };
+
+    if game_loaded {
+        match GAME_LOAD_ORDER.write().unwrap().save(app, &game) {
+            Ok(_) => result.load_order = true,
+            Err(error) => result
+                .errors
+                .push(format!("Error saving the load order: {}", error)),
+        }
+
+        result.profiles = true;
+        for (name, profile) in GAME_PROFILES.write().unwrap().iter_mut() {
+            if let Err(error) = profile.save(app, &game, name) {
+                result.profiles = false;
+                result
+                    .errors
+                    .push(format!("Error saving the profile {}: {}", name, error));
+            }
+        }
+    }
+
+    match SETTINGS.read().unwrap().save(app) {
+        Ok(_) => result.settings = true,
+        Err(error) => result
+            .errors
+            .push(format!("Error saving the settings: {}", error)),
+    }
+
+    if result.game_config && result.load_order {
+        UNSAVED_CHANGES.store(false, Ordering::SeqCst);
+    }
+
+    result
+}
+
+/// Returns what will happen to each movie pack on launch, and why.
+fn movie_pack_handling(
+    app: &tauri::AppHandle,
+    game: &GameInfo,
+    data_path: &Path,
+    game_config: &GameConfig,
+    load_order: &LoadOrder,
+) -> Result<Vec<MoviePackHandling>, String> {
+    // Whether disabled movies in the secondary folder get masked/excluded depends on the folder being loaded at all.
+    let mut folder_list = String::new();
+    let mut pack_list = String::new();
+    load_order
+        .build_load_order_string(
+            app,
+            game_config,
+            game,
+            data_path,
+            &mut pack_list,
+            &mut folder_list,
+        )
+        .map_err(|e| format!("Error building the load order: {}", e))?;
+
+    let secondary_mods_path = secondary_mods_path(app, game.key()).ok();
+    let secondary_in_use = secondary_mods_path
+        .as_ref()
+        .is_some_and(|path| folder_list.contains(&load_order_path_string(path)));
+    let canonical_data_path = path_to_absolute_path(data_path, false);
+    let canonical_secondary_path = secondary_mods_path
+        .as_ref()
+        .map(|path| path_to_absolute_path(path, false));
+    let uses_masking = game_uses_movie_masking(game);
+
+    let mut movies = game_config
+        .mods()
+        .values()
+        .filter(|modd| *modd.pack_type() == PFHFileType::Movie && !modd.paths().is_empty())
+        .collect::<Vec<_>>();
+    movies.sort_by(|a, b| a.id().cmp(b.id()));
+
+    Ok(movies
+        .iter()
+        .map(|modd| {
+            let path = &modd.paths()[0];
+            let pack = path
+                .file_name()
+                .map(|name| name.to_string_lossy().to_string())
+                .unwrap_or_default();
+            let canonical_path = path_to_absolute_path(path, false);
+            let in_data = canonical_path.starts_with(&canonical_data_path);
+            let in_secondary = canonical_secondary_path
+                .as_ref()
+                .is_some_and(|secondary| canonical_path.parent() == Some(secondary.as_path()));
+
+            let (handling, reason) = if modd.enabled(game, data_path) {
+                if in_data && uses_masking {
+                    (
+                        MoviePackHandlingMode::Loaded,
+                        "Movie packs in /data are always loaded in this game.",
+                    )
+                } else {
+                    (MoviePackHandlingMode::Loaded, "Enabled.")
+                }
+            } else if uses_masking {
+                if in_secondary && secondary_in_use {
+                    (
+                        MoviePackHandlingMode::Masked,
+                        "Disabled. This game doesn't support excluding packs, so it'll be masked with an empty pack.",
+                    )
+                } else {
+                    (
+                        MoviePackHandlingMode::NotLoaded,
+                        "Disabled, and its folder is not loaded.",
+                    )
+                }
+            } else if pack_list.contains(&format!("exclude_pack_file \"{}\";", pack)) {
+                (
+                    MoviePackHandlingMode::Excluded,
+                    "Disabled. Its folder is loaded, so it'll be excluded with exclude_pack_file.",
+                )
+            } else {
+                (
+                    MoviePackHandlingMode::NotLoaded,
+                    "Disabled, and its folder is not loaded.",
+                )
+            };
+
+            MoviePackHandling {
+                id: modd.id().to_owned(),
+                pack,
+                handling,
+                reason: reason.to_owned(),
+            }
+        })
+        .collect())
+}
+
+/// Util to get the commit the local copy of the sql scripts repo is at, if it has been downloaded.
+fn sql_scripts_commit(app: &tauri::AppHandle) -> Option<String> {
+    let git_path = sql_scripts_remote_path(app).ok()?.join(".git");
+    let head = std::fs::read_to_string(git_path.join("HEAD")).ok()?;
+    let head = head.trim();
+
+    // Detached heads contain the commit directly. Otherwise, follow the ref, which may be packed.
+    match head.strip_prefix("ref: ") {
+        Some(reference) => match std::fs::read_to_string(git_path.join(reference)) {
+            Ok(commit) => Some(commit.trim().to_owned()),
+            Err(_) => std::fs::read_to_string(git_path.join("packed-refs"))
+                .ok()?
+                .lines()
+                .filter_map(|line| line.split_once(' '))
+                .find(|(_, name)| *name == reference)
+                .map(|(commit, _)| commit.to_owned()),
+        },
+        None => Some(head.to_owned()),
+    }
+}
+
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
     tauri::Builder::default()
@@ -1223,33 +4891,142 @@ pub fn run() {
             // State for the updater.
             app.manage(updater::PendingUpdate(Mutex::new(None)));
 
+            #[cfg(desktop)]
+            if SETTINGS.read().unwrap().check_updates_on_start {
+                updater::check_for_update_on_start(app_handle);
+            }
+
             Ok(())
         })
+        .on_window_event(|window, event| {
+            // Don't lose changes pending due to autosave being disabled when closing the program.
+            if let tauri::WindowEvent::CloseRequested { .. } = event {
+                for flush_error in flush_all_state(window.app_handle()).errors {
+                    error!("{}", flush_error);
+                }
+            }
+        })
         .invoke_handler(tauri::generate_handler![
             launch_game,
+            prepare_launch,
+            set_view_only,
+            save_now,
+            flush_state,
+            get_unsaved_changes,
+            get_last_load_timings,
             get_sidebar_icons,
+            get_games_overview,
+            get_lock_details,
+            can_lock_game,
+            is_game_locked,
+            toggle_game_locked,
+            lock_all_games,
             handle_mod_toggled,
+            handle_mod_toggled_with_diff,
+            handle_category_toggled,
+            invert_enabled_mods,
+            export_enable_states,
+            import_enable_states,
+            migrate_load_order_bin_names,
+            regenerate_map_pack,
+            snapshot_game_config,
+            list_snapshots,
+            restore_snapshot,
+            create_profile,
+            apply_profile,
+            delete_profile,
             handle_mod_category_change,
+            move_mod_to_category_at,
+            set_mod_creator_name,
             init_settings,
             load_settings,
+            reload_settings_from_disk,
             save_settings,
+            set_filter_values,
+            set_open_remote_in_app,
+            get_selection_state,
+            set_selection_state,
+            set_all_categories_open,
             get_available_languages,
             get_available_date_formats,
+            get_about_info,
+            check_schema_compatibility,
             browse_folder,
             handle_change_game_selected,
+            refresh_all_online_metadata,
+            load_packs_with_disabled,
             move_pack_in_load_order_in_direction,
             move_pack_in_load_order,
+            move_movie_above_another,
+            set_manual_movie_order,
+            is_load_order_automatic,
+            set_load_order_automatic,
+            set_load_order_manual,
+            get_requirement_satisfaction,
+            suggest_load_order,
+            apply_suggested_load_order,
             reorder_categories,
             open_mod_folder,
             open_mod_url,
+            open_sql_scripts_folder,
             create_category,
             rename_category,
             remove_category,
+            remove_empty_categories,
+            get_pack_type_changes,
+            find_uncategorized_mods,
+            fix_uncategorized,
+            bulk_rename_local_mods,
+            export_state_json,
+            load_order_hash,
+            check_case_mismatches,
+            parse_workshop_url,
+            import_load_order_from_url,
+            export_load_order,
+            import_load_order,
+            copy_mods_to_data,
+            copy_mods_to_secondary,
+            move_mods_to_secondary,
+            prepare_game_folders,
+            supports_secondary_folder,
+            check_secondary_folder_placement,
+            get_game_capabilities,
+            supported_launch_options,
             get_launch_options,
+            refresh_launch_options,
+            set_script_enabled,
+            get_script_conflicts,
+            clear_extracted_scripts_cache,
+            export_conflict_report,
+            save_launch_preset,
+            apply_launch_preset,
+            list_launch_presets,
+            set_translation_language,
+            get_rebalancer_candidates,
+            get_active_sql_scripts,
+            get_script_parse_errors,
+            preview_patcher_command,
+            get_movie_pack_handling,
+            get_masked_movies,
+            get_mod_description,
+            get_mod_location_detail,
+            get_effective_override_rank,
+            find_mod_by_pack_name,
+            filter_mods,
+            find_duplicate_content,
+            get_duplicated_mods,
+            get_map_mods_status,
+            set_preferred_mod_location,
             request_mod_remote_metadata,
+            compare_with_subscriptions,
+            get_workshop_summary,
+            download_subscribed_mods,
+            redownload_workshop_mod,
             mod_tags_available,
             upload_mod,
             #[cfg(desktop)]
+            updater::check_for_update,
+            #[cfg(desktop)]
             updater::fetch_update,
             #[cfg(desktop)]
             updater::install_update
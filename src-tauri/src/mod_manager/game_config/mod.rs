@@ -18,18 +18,20 @@ use serde::{Deserialize, Serialize};
 use serde_json::to_string_pretty;
 use tauri::async_runtime::Receiver;
 
-use std::collections::{BTreeMap, HashMap};
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::fs::{DirBuilder, File};
 use std::io::{BufReader, BufWriter, Cursor, Read, Write};
 use std::path::Path;
-use std::time::UNIX_EPOCH;
+use std::sync::atomic::Ordering;
+use std::time::{Instant, UNIX_EPOCH};
 
 use rpfm_lib::files::pack::Pack;
 use rpfm_lib::games::{GameInfo, pfh_file_type::PFHFileType, supported_games::KEY_SHOGUN_2};
 use rpfm_lib::integrations::log::error;
+use rpfm_lib::utils::path_to_absolute_string;
 
 use crate::mod_manager::{integrations::TxStoreResponse, load_order::LoadOrder, mods::Mod};
-use crate::{GAME_SELECTED, INTEGRATIONS, SETTINGS, settings::*};
+use crate::{GAME_SELECTED, INTEGRATIONS, SETTINGS, UNSAVED_CHANGES, settings::*};
 use crate::{RESERVED_PACK_NAME, RESERVED_PACK_NAME_ALTERNATIVE};
 
 use super::integrations::{Integrations, StoreId};
@@ -65,6 +67,24 @@ pub struct GameConfig {
 
     // List of categories in order.
     categories_order: Vec<String>,
+
+    // Time spent on each phase of the last mod list update.
+    #[serde(skip)]
+    scan_timings: ScanTimings,
+
+    // Mods whose pack type changed between scans, with their old and new types. Kept until the config is reloaded.
+    #[serde(skip)]
+    pack_type_changes: BTreeMap<String, (PFHFileType, PFHFileType)>,
+}
+
+/// Time spent, in milliseconds, on each phase of a mod list update.
+#[derive(Clone, Copy, Debug, Default, Getters)]
+#[getset(get_copy = "pub")]
+pub struct ScanTimings {
+    content_scan: u64,
+    online_request: u64,
+    secondary_scan: u64,
+    data_scan: u64,
 }
 
 //-------------------------------------------------------------------------------//
@@ -136,6 +156,16 @@ impl GameConfig {
         Ok(())
     }
 
+    /// Saves the game config, unless autosave is disabled. In that case, it's just marked as having unsaved changes.
+    pub fn save_or_defer(&mut self, app_handle: &tauri::AppHandle, game: &GameInfo) -> Result<()> {
+        if SETTINGS.read().unwrap().autosave {
+            self.save(app_handle, game)
+        } else {
+            UNSAVED_CHANGES.store(true, Ordering::SeqCst);
+            Ok(())
+        }
+    }
+
     #[allow(dead_code)]
     pub fn update(_game_name: &str) -> Result<()> {
         //let _ = versions::v0::GameConfigV0::update(game_name);
@@ -242,6 +272,160 @@ impl GameConfig {
         Ok(())
     }
 
+    /// Moves the provided mods to a category, inserting them at the provided index of the category in the same order they were passed.
+    ///
+    /// The index is relative to the category's list without the moved mods, so moving mods within the same category works too.
+    pub fn move_mods_to_category_at(
+        &mut self,
+        mod_ids: &[String],
+        category: &str,
+        index: usize,
+    ) -> Result<()> {
+        if !self.categories().contains_key(category) {
+            return Err(anyhow!("Category {} not found", category));
+        }
+
+        // Unknown mods can't be shown in the tree, and duplicated ones would be shown twice.
+        let mut seen = HashSet::new();
+        let mod_ids = mod_ids
+            .iter()
+            .filter(|id| self.mods().contains_key(*id) && seen.insert(*id))
+            .cloned()
+            .collect::<Vec<_>>();
+
+        for mods in self.categories_mut().values_mut() {
+            mods.retain(|x| !mod_ids.contains(x));
+        }
+
+        if let Some(target_mods) = self.categories_mut().get_mut(category) {
+            let index = index.min(target_mods.len());
+            target_mods.splice(index..index, mod_ids.iter().cloned());
+        }
+
+        Ok(())
+    }
+
+    /// Returns the installed mods that are not in any category, sorted by id. These mods are not shown in the mod tree.
+    pub fn uncategorized_mods(&self) -> Vec<String> {
+        let mut mods = self
+            .mods()
+            .values()
+            .filter(|modd| {
+                !modd.paths().is_empty()
+                    && self
+                        .categories()
+                        .values()
+                        .all(|mods| !mods.contains(modd.id()))
+            })
+            .map(|modd| modd.id().to_owned())
+            .collect::<Vec<_>>();
+
+        mods.sort();
+        mods
+    }
+
+    /// Moves all the installed mods not in any category to the default category. Returns the moved mods.
+    pub fn fix_uncategorized_mods(&mut self) -> Vec<String> {
+        let uncategorized = self.uncategorized_mods();
+
+        self.categories_mut()
+            .entry(DEFAULT_CATEGORY.to_owned())
+            .or_default()
+            .extend(uncategorized.iter().cloned());
+
+        if !self
+            .categories_order()
+            .iter()
+            .any(|category| category == DEFAULT_CATEGORY)
+        {
+            self.categories_order_mut()
+                .push(DEFAULT_CATEGORY.to_owned());
+        }
+
+        uncategorized
+    }
+
+    /// Removes every category with no mods in it, except the default one. Returns the removed categories.
+    pub fn remove_empty_categories(&mut self) -> Vec<String> {
+        let empty = self
+            .categories()
+            .iter()
+            .filter(|(category, mods)| *category != DEFAULT_CATEGORY && mods.is_empty())
+            .map(|(category, _)| category.to_owned())
+            .collect::<Vec<_>>();
+
+        for category in &empty {
+            self.categories_mut().remove(category);
+        }
+
+        self.categories_order_mut().retain(|x| !empty.contains(x));
+
+        empty
+    }
+
+    /// Renames all the files of a mod on disk, and moves its data and category to the new id.
+    ///
+    /// If any of the files fails to be renamed, the ones already renamed are restored.
+    pub fn rename_mod(&mut self, id: &str, new_id: &str) -> Result<()> {
+        let mut modd = self
+            .mods()
+            .get(id)
+            .cloned()
+            .ok_or_else(|| anyhow!("Mod {} not found.", id))?;
+
+        let new_paths = modd
+            .paths()
+            .iter()
+            .map(|path| path.with_file_name(new_id))
+            .collect::<Vec<_>>();
+
+        if let Some(path) = new_paths.iter().find(|path| path.exists()) {
+            return Err(anyhow!("File {} already exists.", path.to_string_lossy()));
+        }
+
+        for (index, (path, new_path)) in modd.paths().iter().zip(new_paths.iter()).enumerate() {
+            if let Err(error) = std::fs::rename(path, new_path) {
+                for (path, new_path) in modd.paths().iter().zip(new_paths.iter()).take(index) {
+                    let _ = std::fs::rename(new_path, path);
+                }
+
+                return Err(error.into());
+            }
+        }
+
+        // Rename the png too, or the mod loses its preview.
+        for (path, new_path) in modd.paths().iter().zip(new_paths.iter()) {
+            let mut old_image_path = path.to_path_buf();
+            old_image_path.set_extension("png");
+
+            let mut new_image_path = new_path.to_path_buf();
+            new_image_path.set_extension("png");
+
+            if old_image_path.is_file() && !new_image_path.exists() {
+                let _ = std::fs::rename(&old_image_path, &new_image_path);
+            }
+        }
+
+        // Local mods are named after their pack. Mods with a name from their store keep it.
+        if modd.name() == id {
+            modd.set_name(new_id.to_owned());
+        }
+
+        modd.set_id(new_id.to_owned());
+        modd.set_paths(new_paths);
+
+        self.mods_mut().remove(id);
+        self.mods_mut().insert(new_id.to_owned(), modd);
+
+        for mods in self.categories_mut().values_mut() {
+            mods.iter_mut()
+                .filter(|mod_id| *mod_id == id)
+                .for_each(|mod_id| *mod_id = new_id.to_owned());
+        }
+
+        Ok(())
+    }
+
     /// NOTE: This returns a channel receiver for the workshop/equivalent service data request.
     /// This is done so the request doesn't hang the entire load process, as it usually takes 2 or 3 seconds to complete.
     pub async fn update_mod_list(
@@ -253,6 +437,15 @@ impl GameConfig {
         skip_network_update: bool,
     ) -> Result<Option<Receiver<TxStoreResponse>>> {
         let mut receiver = None;
+        let mut timings = ScanTimings::default();
+
+        // Keep the pack types of the installed mods, so we can detect mods changing their type.
+        let previous_pack_types = self
+            .mods()
+            .values()
+            .filter(|modd| !modd.paths().is_empty())
+            .map(|modd| (modd.id().to_owned(), *modd.pack_type()))
+            .collect::<HashMap<_, _>>();
 
         // Clear the mod paths, just in case a failure while loading them leaves them unclean.
         self.mods_mut()
@@ -272,6 +465,7 @@ impl GameConfig {
                 let secondary_mods_paths = secondary_mods_packs_paths(app_handle, game.key());
 
                 let mut steam_ids = vec![];
+                let start = Instant::now();
 
                 // Initialize the mods in the contents folders first.
                 //
@@ -492,6 +686,9 @@ impl GameConfig {
                     }
                 }
 
+                timings.content_scan = start.elapsed().as_millis() as u64;
+                let start = Instant::now();
+
                 // Ignore network population errors for now.
                 if !skip_network_update {
                     let integrations = (*INTEGRATIONS.lock().unwrap()).clone();
@@ -502,6 +699,9 @@ impl GameConfig {
                     );
                 }
 
+                timings.online_request = start.elapsed().as_millis() as u64;
+                let start = Instant::now();
+
                 // Then, if the game supports secondary mod path (only since Shogun 2) we check for mods in there. These have middle priority.
                 //
                 // Non supported games will simply return "None" here.
@@ -644,6 +844,9 @@ impl GameConfig {
                     }
                 }
 
+                timings.secondary_scan = start.elapsed().as_millis() as u64;
+                let start = Instant::now();
+
                 // Then finally we process /data packs. These have the highest priority.
                 if let Some(ref paths) = data_paths {
                     let paths = paths
@@ -808,9 +1011,47 @@ impl GameConfig {
                         }
                     }
                 }
+
+                timings.data_scan = start.elapsed().as_millis() as u64;
             }
         }
 
+        self.scan_timings = timings;
+
+        for (id, old_type) in previous_pack_types {
+            if let Some(modd) = self.mods().get(&id) {
+                if !modd.paths().is_empty() && *modd.pack_type() != old_type {
+                    // Keep the oldest type, so multiple changes between reloads show up as one.
+                    let new_type = *modd.pack_type();
+                    let first_type = self
+                        .pack_type_changes
+                        .remove(&id)
+                        .map_or(old_type, |change| change.0);
+
+                    if first_type != new_type {
+                        self.pack_type_changes.insert(id, (first_type, new_type));
+                    }
+                }
+            }
+        }
+
+        // Legacy mods may be split if their pack was found before their bin. Merge them here, so it doesn't depend on online data.
+        self.remove_split_legacy_mods();
+
+        // Respect the user's choice of location for mods installed in more than one place.
+        if let Ok(data_path) = game.data_path(game_path) {
+            let data_path = path_to_absolute_string(&data_path);
+            let secondary_path = path_to_absolute_string(
+                &secondary_mods_path(app_handle, game.key()).unwrap_or_default(),
+            );
+            let content_path =
+                path_to_absolute_string(&game.content_path(game_path).unwrap_or_default());
+
+            self.mods_mut().values_mut().for_each(|modd| {
+                modd.apply_preferred_location(&data_path, &secondary_path, &content_path)
+            });
+        }
+
         // Update the categories list to remove any mod that has no path, and add any new mod to the default category.
         for mods in self.categories.values_mut() {
             mods.retain(|mod_id| match self.mods.get(mod_id) {
@@ -819,25 +1060,7 @@ impl GameConfig {
             });
         }
 
-        let mut mods_to_add = vec![];
-        for modd in self.mods.values() {
-            if !modd.paths().is_empty()
-                && self
-                    .categories()
-                    .iter()
-                    .all(|(_, mods)| !mods.contains(modd.id()))
-            {
-                mods_to_add.push(modd.id().to_owned());
-            }
-        }
-
-        match self.categories_mut().get_mut(DEFAULT_CATEGORY) {
-            Some(mods) => mods.append(&mut mods_to_add),
-            None => {
-                self.categories_mut()
-                    .insert(DEFAULT_CATEGORY.to_owned(), mods_to_add);
-            }
-        }
+        self.fix_uncategorized_mods();
 
         // If we got a default category, make sure it's always at the end.
         if let Some(cat) = self.categories_order().last() {
@@ -851,15 +1074,78 @@ impl GameConfig {
 
         // Update the current load order to reflect any change related to mods no longer being installed or being added as new.
         let game_data_path = game.data_path(game_path)?;
+        load_order.migrate_bin_names(self);
         load_order.update(app_handle, self, game, &game_data_path);
-        load_order.save(app_handle, game)?;
+        load_order.save_or_defer(app_handle, game)?;
 
         // Save the GameConfig or we may lost the population.
-        self.save(app_handle, game)?;
+        self.save_or_defer(app_handle, game)?;
 
         Ok(receiver)
     }
 
+    /// Copies the online data of a copy of this config into its mods,
+    /// keeping any change the user did to this one while the online data was being retrieved.
+    ///
+    /// Mods renamed or removed in the meantime are not in this config anymore, so they're skipped.
+    pub fn merge_online_data(&mut self, updated: GameConfig) {
+        for (id, modd) in updated.mods() {
+            if let Some(current) = self
+                .mods_mut()
+                .get_mut(id)
+                .filter(|current| *current.store_id() != StoreId::None)
+                .filter(|current| current.store_id() == modd.store_id())
+            {
+                current.copy_online_data(modd);
+
+                // Converting legacy and map mods adds the converted pack to their paths, so keep it.
+                let new_paths = modd
+                    .paths()
+                    .iter()
+                    .filter(|path| !current.paths().contains(path))
+                    .cloned()
+                    .collect::<Vec<_>>();
+                current.paths_mut().splice(0..0, new_paths);
+                current.set_pack_type(*modd.pack_type());
+            }
+        }
+
+        self.remove_split_legacy_mods();
+    }
+
+    /// Merges legacy mods split into two distinct mods (one for the bin, and one for the pack converted from it)
+    /// back into the bin one. Returns the ids of the removed mods.
+    pub fn remove_split_legacy_mods(&mut self) -> Vec<String> {
+        let alt_names = self
+            .mods()
+            .values()
+            .filter_map(|modd| Some((modd.id().to_owned(), modd.alt_name()?)))
+            .filter(|(id, alt_name)| id != alt_name)
+            .collect::<Vec<_>>();
+
+        let mut removed = vec![];
+        for (id, alt_name) in alt_names {
+            if let Some(split) = self.mods_mut().remove(&alt_name) {
+                // Keep the paths of the split mod, or we'll lose the converted pack until the next scan.
+                if let Some(modd) = self.mods_mut().get_mut(&id) {
+                    for path in split.paths().iter().rev() {
+                        if !modd.paths().contains(path) {
+                            modd.paths_mut().insert(0, path.to_path_buf());
+                        }
+                    }
+                }
+
+                self.categories_mut().iter_mut().for_each(|(_, mods)| {
+                    mods.retain(|modd| modd != &alt_name);
+                });
+
+                removed.push(alt_name);
+            }
+        }
+
+        removed
+    }
+
     pub async fn update_mod_list_with_online_data(
         &mut self,
         tx_recv: Receiver<TxStoreResponse>,
@@ -983,20 +1269,7 @@ impl GameConfig {
 
                     // Before continuing, we need to do some cleaning. There's a chance that due to the order of operations done to populate the mod list
                     // Some legacy packs get split into two distinct mods. We need to detect them and clean them up here.
-                    let alt_names = self
-                        .mods()
-                        .par_iter()
-                        .filter_map(|(_, modd)| modd.alt_name())
-                        .collect::<Vec<_>>();
-
-                    for alt_name in &alt_names {
-                        self.mods_mut().remove(alt_name);
-                        self.categories_mut().iter_mut().for_each(|(_, mods)| {
-                            mods.retain(|modd| modd != alt_name);
-                        });
-                    }
-
-                    self.save(app, &game)?;
+                    self.remove_split_legacy_mods();
                 }
             }
             Err(error) => return Err(anyhow!("Failed to get data from store: {}", error)),
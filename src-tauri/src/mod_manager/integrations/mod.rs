@@ -126,6 +126,12 @@ trait Integration {
 }
 
 pub enum TxStoreSend {
+    DownloadSubscribedMods(
+        Sender<TxStoreResponse>,
+        AppHandle,
+        GameInfo,
+        Option<Vec<String>>,
+    ),
     LaunchGame(Sender<TxStoreResponse>, AppHandle, GameInfo, String, bool),
     RequestRemoteModData(Sender<TxStoreResponse>, AppHandle, GameInfo, Vec<String>),
     RequestModRemoteMetadata(Sender<TxStoreResponse>, AppHandle, GameInfo, StoreId),
@@ -171,6 +177,15 @@ pub struct RemoteMetadata {
     //pub preview: String,
 }
 
+/// Details about the file used to lock a game, to find out why locking it may fail.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct GameLockDetails {
+    pub path: String,
+    pub exists: bool,
+    pub readonly: bool,
+    pub writable: bool,
+}
+
 //-------------------------------------------------------------------------------//
 //                             Implementations
 //-------------------------------------------------------------------------------//
@@ -212,6 +227,67 @@ impl Integrations {
         }
     }
 
+    /// Returns if the game can be locked to prevent updates, and if it's currently locked.
+    pub fn game_locked_state(game: &GameInfo, game_path: &Path) -> (bool, bool) {
+        (
+            Self::wrapper_can_game_locked(game, game_path),
+            Self::wrapper_is_game_locked(game, game_path),
+        )
+    }
+
+    /// Returns the remote ids of all the mods the user is subscribed to in the store. Blocks until the store answers.
+    pub fn subscribed_mod_ids(app_handle: &AppHandle, game: &GameInfo) -> Result<Vec<String>> {
+        SteamIntegration::subscribed_items(app_handle, game)
+    }
+
+    /// Removes the downloaded files of the provided mods and tells the store to download them again, then waits for it to finish.
+    ///
+    /// Useful when the files of a mod got corrupted or were only partially downloaded.
+    pub fn redownload_mods(
+        app_handle: &AppHandle,
+        game: &GameInfo,
+        remote_ids: &[String],
+    ) -> Result<()> {
+        SteamIntegration::redownload_items(app_handle, game, remote_ids)
+    }
+
+    /// Locks or unlocks a game, returning if it's locked after the change. Unlike the wrapper, this reports why it failed.
+    pub fn set_game_locked(game: &GameInfo, game_path: &Path, lock: bool) -> Result<bool> {
+        SteamIntegration::toggle_game_locked(game, game_path, lock)
+    }
+
+    /// Returns the description of a mod, ready to be displayed.
+    ///
+    /// Store-specific markup is converted when the mod data is fetched, so this doesn't convert it again.
+    pub fn mod_description(modd: &Mod) -> String {
+        modd.description().to_owned()
+    }
+
+    /// Returns the details of the file used to lock the game.
+    pub fn game_lock_details(game: &GameInfo, game_path: &Path) -> Result<GameLockDetails> {
+        SteamIntegration::game_lock_details(game, game_path)
+    }
+
+    recv!(download_subscribed_mods, Success, ());
+    pub async fn download_subscribed_mods(
+        &self,
+        app: &AppHandle,
+        game: &GameInfo,
+        published_file_ids: &Option<Vec<String>>,
+    ) -> Receiver<TxStoreResponse> {
+        let (tx_send, tx_recv) = channel(32);
+        let _ = self
+            .sender
+            .send(TxStoreSend::DownloadSubscribedMods(
+                tx_send,
+                app.clone(),
+                game.clone(),
+                published_file_ids.clone(),
+            ))
+            .await;
+        tx_recv
+    }
+
     recv!(launch_game, Success, ());
     pub async fn launch_game(
         &self,
@@ -349,6 +425,22 @@ impl Integrations {
                     }
                 }
 
+                Some(TxStoreSend::DownloadSubscribedMods(
+                    tx_send,
+                    app,
+                    game,
+                    published_file_ids,
+                )) => {
+                    match Self::wrapper_download_subscribed_mods(&app, &game, &published_file_ids) {
+                        Ok(data) => {
+                            let _ = tx_send.send(TxStoreResponse::Success(data)).await;
+                        }
+                        Err(e) => {
+                            let _ = tx_send.send(TxStoreResponse::Error(e)).await;
+                        }
+                    }
+                }
+
                 Some(TxStoreSend::RequestModRemoteMetadata(tx_send, app, game, remote_id)) => {
                     match Self::wrapper_request_mod_remote_metadata(&app, &game, &remote_id) {
                         Ok(data) => {
@@ -447,14 +539,14 @@ impl Integrations {
     ) -> Result<()> {
         SteamIntegration::launch_game(app_handle, game, command_to_pass, wait_for_finish)
     }
-    /*
+
     fn wrapper_download_subscribed_mods(
         app_handle: &tauri::AppHandle,
         game: &GameInfo,
         published_file_ids: &Option<Vec<String>>,
     ) -> Result<()> {
         SteamIntegration::download_subscribed_mods(app_handle, game, published_file_ids)
-    }*/
+    }
 
     fn wrapper_store_user_id(app_handle: &tauri::AppHandle, game: &GameInfo) -> Result<String> {
         SteamIntegration::user_id(app_handle, game)
@@ -11,7 +11,7 @@
 use anyhow::{Result, anyhow};
 use base64::prelude::*;
 use interprocess::local_socket::{GenericNamespaced, ListenerOptions, prelude::*};
-use regex::Regex;
+use regex::{Captures, Regex};
 use serde::Deserialize;
 use steam_workshop_api::{client::Workshop, interfaces::i_steam_user::*};
 use tauri::AppHandle;
@@ -35,13 +35,77 @@ use crate::settings::config_path;
 
 #[cfg(target_os = "windows")]
 use super::{CREATE_NEW_CONSOLE, CREATE_NO_WINDOW, DETACHED_PROCESS};
-use super::{Integration, RemoteMetadata, PublishedFileVisibilityDerive, StoreId};
+use super::{GameLockDetails, Integration, RemoteMetadata, PublishedFileVisibilityDerive, StoreId};
 
 #[cfg(any(target_os = "linux", target_os = "macos"))]
 use std::os::unix::fs::PermissionsExt;
 
-const REGEX_URL: LazyCell<Regex> =
-    LazyCell::new(|| Regex::new(r"(\[url=)(.*)(\])(.*)(\[/url\])").unwrap());
+// The text can't contain another url tag, so nested tags are matched from the inside out.
+const REGEX_URL: LazyCell<Regex> = LazyCell::new(|| {
+    Regex::new(r"(?s)(\[url=)([^\]]*)(\])((?:[^\[]|\[[^u]|\[u[^r]|\[ur[^l])*?)(\[/url\])").unwrap()
+});
+
+// BBCode tags that translate directly to html. Inline ones go before the rest, blocks after the lists.
+const REGEX_BBCODE_INLINE: LazyCell<Vec<(Regex, &str)>> = LazyCell::new(|| {
+    vec![
+        (Regex::new(r"\[h1\](.*?)\[/h1\]").unwrap(), "<h1>$1</h1>"),
+        (Regex::new(r"\[h2\](.*?)\[/h2\]").unwrap(), "<h2>$1</h2>"),
+        (Regex::new(r"\[h3\](.*?)\[/h3\]").unwrap(), "<h3>$1</h3>"),
+        (
+            Regex::new(r"\[b\](.*?)\[/b\]").unwrap(),
+            "<strong>$1</strong>",
+        ),
+        (Regex::new(r"\[u\](.*?)\[/u\]").unwrap(), "<u>$1</u>"),
+        (Regex::new(r"\[i\](.*?)\[/i\]").unwrap(), "<em>$1</em>"),
+        (
+            Regex::new(r"\[strike\](.*?)\[/strike\]").unwrap(),
+            "<del>$1</del>",
+        ),
+        (
+            Regex::new(r"\[spoiler\](.*?)\[/spoiler\]").unwrap(),
+            "<span class=\"spoiler\" onclick=\"this.classList.toggle('revealed')\">$1</span>",
+        ),
+    ]
+});
+const REGEX_BBCODE_BLOCKS: LazyCell<Vec<(Regex, &str)>> = LazyCell::new(|| {
+    vec![
+        (
+            Regex::new(r"(?s)\[quote=([^\]]+)\](.*?)\[/quote\]").unwrap(),
+            "<blockquote><p><em>Originally posted by <strong>$1</strong>:</em></p><p>$2</p></blockquote>",
+        ),
+        (
+            Regex::new(r"(?s)\[code\](.*?)\[/code\]").unwrap(),
+            "<pre><code>$1</code></pre>",
+        ),
+    ]
+});
+const REGEX_BBCODE_TABLE_CELLS: LazyCell<Vec<(Regex, &str)>> = LazyCell::new(|| {
+    vec![
+        (
+            Regex::new(r"(?s)\[tr\](.*?)\[/tr\]").unwrap(),
+            "<tr>$1</tr>",
+        ),
+        (
+            Regex::new(r"(?s)\[th\](.*?)\[/th\]").unwrap(),
+            "<th>$1</th>",
+        ),
+        (
+            Regex::new(r"(?s)\[td\](.*?)\[/td\]").unwrap(),
+            "<td>$1</td>",
+        ),
+    ]
+});
+const REGEX_NOPARSE: LazyCell<Regex> =
+    LazyCell::new(|| Regex::new(r"\[noparse\](.*?)\[/noparse\]").unwrap());
+const REGEX_LIST: LazyCell<Regex> =
+    LazyCell::new(|| Regex::new(r"(?s)\[list\](.*?)\[/list\]").unwrap());
+const REGEX_LIST_UNFINISHED: LazyCell<Regex> =
+    LazyCell::new(|| Regex::new(r"(?s)\[list\](.*)$").unwrap());
+const REGEX_OLIST: LazyCell<Regex> =
+    LazyCell::new(|| Regex::new(r"(?s)\[olist\](.*?)\[/olist\]").unwrap());
+const REGEX_TABLE: LazyCell<Regex> =
+    LazyCell::new(|| Regex::new(r"(?s)\[table(?:=([^\]]+))?\](.*?)\[/table\]").unwrap());
+const REGEX_IMG: LazyCell<Regex> = LazyCell::new(|| Regex::new(r"\[img\](.*?)\[/img\]").unwrap());
 const WORKSHOPPER_PATH: LazyCell<String> = LazyCell::new(|| {
     if cfg!(debug_assertions) {
         format!("./target/debug/{}", WORKSHOPPER_EXE)
@@ -55,6 +119,7 @@ const WORKSHOPPER_PATH: LazyCell<String> = LazyCell::new(|| {
 #[cfg(target_os = "windows")] const SCRIPT_UPLOAD_TO_WORKSHOP: &str = "upload-to-workshop.bat";
 #[cfg(target_os = "windows")] const SCRIPT_GET_PUBLISHED_FILE_DETAILS: &str = "get-published-file-details.bat";
 #[cfg(target_os = "windows")] const SCRIPT_GET_USER_ID: &str = "get-user-id.bat";
+#[cfg(target_os = "windows")] const SCRIPT_GET_SUBSCRIBED_ITEMS: &str = "get-subscribed-items.bat";
 #[cfg(target_os = "windows")] const SCRIPT_LAUNCH_GAME: &str = "launch-game.bat";
 
 #[cfg(any(target_os = "linux", target_os = "macos"))] const STEAM_PROCESS_NAME: &str = "steam";
@@ -62,6 +127,7 @@ const WORKSHOPPER_PATH: LazyCell<String> = LazyCell::new(|| {
 #[cfg(any(target_os = "linux", target_os = "macos"))] const SCRIPT_UPLOAD_TO_WORKSHOP: &str = "upload-to-workshop.sh";
 #[cfg(any(target_os = "linux", target_os = "macos"))] const SCRIPT_GET_PUBLISHED_FILE_DETAILS: &str = "get-published-file-details.sh";
 #[cfg(any(target_os = "linux", target_os = "macos"))] const SCRIPT_GET_USER_ID: &str = "get-user-id.sh";
+#[cfg(any(target_os = "linux", target_os = "macos"))] const SCRIPT_GET_SUBSCRIBED_ITEMS: &str = "get-subscribed-items.sh";
 #[cfg(any(target_os = "linux", target_os = "macos"))] const SCRIPT_LAUNCH_GAME: &str = "launch-game.sh";
 //-------------------------------------------------------------------------------//
 //                              Enums & Structs
@@ -199,7 +265,7 @@ impl Integration for SteamIntegration {
             modd.set_creator(workshop_item.owner.to_string());
             modd.set_file_name(workshop_item.file_name.to_owned());
             modd.set_file_size(workshop_item.file_size as u64);
-            modd.set_description(workshop_item.description.to_owned());
+            modd.set_description(Self::format_description(&workshop_item.description));
             modd.set_time_created(workshop_item.time_created as usize);
             modd.set_time_updated(workshop_item.time_updated as usize);
 
@@ -385,35 +451,6 @@ impl Integration for SteamIntegration {
 
         Ok(())
     }
-    /*
-    /// This function asks workshopper to get all subscribed items, check which ones are missing, and tell steam to re-download them.
-    pub fn download_subscribed_mods(
-        app: &AppHandle,
-        game: &GameInfo,
-        published_file_ids: &Option<Vec<String>>,
-    ) -> Result<()> {
-        let settings = SETTINGS.read().unwrap().clone();
-        let game_path = settings.game_path(game)?;
-        let steam_id = game.steam_id(&game_path)? as u32;
-
-        let mut command = workshopper_command(app, false, true, false)?;
-        command.arg(&*WORKSHOPPER_PATH);
-
-        command.arg("download-subscribed-items");
-        command.arg("-s");
-        command.arg(steam_id.to_string());
-
-        if let Some(published_file_ids) = published_file_ids {
-            command.arg("-p");
-            command.arg(published_file_ids.join(","));
-        }
-
-        workshopper_command_post(&mut command, false, true, false);
-        let mut handle = command.spawn()?;
-        handle.wait()?;
-
-        Ok(())
-    }*/
 
     fn user_id(app: &AppHandle, game: &GameInfo) -> Result<String> {
         if !is_steam_running() {
@@ -483,6 +520,218 @@ impl Integration for SteamIntegration {
     }
 }
 
+impl SteamIntegration {
+    /// This function converts the Steam BBCode of a description into html, so it can be shown as-is.
+    ///
+    /// Links open outside the program, so clicking them doesn't replace the UI.
+    pub fn format_description(description: &str) -> String {
+        let mut html = description.replace("\r\n", "<br/>").replace('\n', "<br/>");
+
+        for (regex, replacement) in REGEX_BBCODE_INLINE.iter() {
+            html = regex.replace_all(&html, *replacement).to_string();
+        }
+
+        html = REGEX_NOPARSE
+            .replace_all(&html, |captures: &Captures| {
+                captures[1].replace('<', "&lt;").replace('>', "&gt;")
+            })
+            .to_string();
+
+        html = html.replace("[hr][/hr]", "<hr>");
+
+        // Each pass only converts the innermost url tags, so we repeat it until there are no tags left.
+        let regex = &*REGEX_URL;
+        while regex.is_match(&html) {
+            html = regex
+                .replace_all(&html, |captures: &Captures| {
+                    format!(
+                        "<a href=\"{}\" target=\"_blank\">{}</a>",
+                        escape_html_attribute(&captures[2]),
+                        &captures[4]
+                    )
+                })
+                .to_string();
+        }
+
+        html = REGEX_LIST
+            .replace_all(&html, |captures: &Captures| {
+                format!("<ul>{}</ul>", list_items(&captures[1]))
+            })
+            .to_string();
+
+        // Some mods never close their lists.
+        html = REGEX_LIST_UNFINISHED
+            .replace_all(&html, |captures: &Captures| {
+                format!("<ul>{}</ul>", list_items(&captures[1]))
+            })
+            .to_string();
+
+        html = REGEX_OLIST
+            .replace_all(&html, |captures: &Captures| {
+                format!("<ol>{}</ol>", list_items(captures[1].trim()))
+            })
+            .to_string();
+
+        for (regex, replacement) in REGEX_BBCODE_BLOCKS.iter() {
+            html = regex.replace_all(&html, *replacement).to_string();
+        }
+
+        html = REGEX_TABLE
+            .replace_all(&html, |captures: &Captures| {
+                let classes = captures
+                    .get(1)
+                    .map(|attributes| {
+                        attributes
+                            .as_str()
+                            .split(' ')
+                            .filter_map(|attribute| match attribute {
+                                "noborder=1" => Some("class=\"no-border\""),
+                                "equalcells=1" => Some("class=\"equal-cells\""),
+                                _ => None,
+                            })
+                            .collect::<Vec<_>>()
+                            .join(" ")
+                    })
+                    .unwrap_or_default();
+
+                let mut content = captures[2].to_owned();
+                for (regex, replacement) in REGEX_BBCODE_TABLE_CELLS.iter() {
+                    content = regex.replace_all(&content, *replacement).to_string();
+                }
+
+                format!("<table {}>{}</table>", classes, content)
+            })
+            .to_string();
+
+        REGEX_IMG
+            .replace_all(&html, |captures: &Captures| {
+                let src = escape_html_attribute(&captures[1]);
+                format!(
+                    "<img src=\"{src}\" alt=\"{src}\" style=\"max-width: 100%; height: auto;\">"
+                )
+            })
+            .to_string()
+    }
+
+    /// This function returns the details of the appmanifest file used to lock the game.
+    ///
+    /// To know if it's writable we re-apply its current permissions, which fails the same way toggling them would.
+    pub fn game_lock_details(game: &GameInfo, game_path: &Path) -> Result<GameLockDetails> {
+        let app_path = app_manifest_path(game, game_path)?;
+        let mut details = GameLockDetails {
+            path: path_to_absolute_string(&app_path),
+            ..Default::default()
+        };
+
+        if app_path.is_file() {
+            let permissions = app_path.metadata()?.permissions();
+            details.exists = true;
+            details.readonly = permissions.readonly();
+            details.writable = std::fs::set_permissions(&app_path, permissions).is_ok();
+        }
+
+        Ok(details)
+    }
+
+    /// This function asks workshopper to get all subscribed items, check which ones are missing, and tell steam to re-download them.
+    pub fn download_subscribed_mods(
+        app: &AppHandle,
+        game: &GameInfo,
+        published_file_ids: &Option<Vec<String>>,
+    ) -> Result<()> {
+        if !is_steam_running() {
+            return Err(anyhow!("Steam is not running."));
+        }
+
+        let settings = SETTINGS.read().unwrap().clone();
+        let game_path = settings.game_path(game)?;
+        let steam_id = game.steam_id(&game_path)? as u32;
+
+        let mut command = workshopper_command(app, false, true, false)?;
+        command.arg(&*WORKSHOPPER_PATH);
+
+        command.arg("download-subscribed-items");
+        command.arg("-s");
+        command.arg(steam_id.to_string());
+
+        if let Some(published_file_ids) = published_file_ids {
+            command.arg("-p");
+            command.arg(published_file_ids.join(","));
+        }
+
+        workshopper_command_post(&mut command, false, true, false);
+        let mut handle = command.spawn()?;
+        let status = handle.wait()?;
+        if !status.success() {
+            return Err(anyhow!(
+                "Error downloading the subscribed items from Steam."
+            ));
+        }
+
+        Ok(())
+    }
+
+    /// This function removes the downloaded files of the provided items, then asks Steam to download them again.
+    ///
+    /// Steam only downloads missing items, so without removing them first broken files are never replaced.
+    pub fn redownload_items(
+        app: &AppHandle,
+        game: &GameInfo,
+        published_file_ids: &[String],
+    ) -> Result<()> {
+        if !is_steam_running() {
+            return Err(anyhow!("Steam is not running."));
+        }
+
+        let settings = SETTINGS.read().unwrap().clone();
+        let game_path = settings.game_path(game)?;
+        let content_path = game.content_path(&game_path)?;
+
+        for published_file_id in published_file_ids {
+            let item_path = content_path.join(published_file_id);
+            if item_path.is_dir() {
+                std::fs::remove_dir_all(&item_path)?;
+            }
+        }
+
+        Self::download_subscribed_mods(app, game, &Some(published_file_ids.to_vec()))
+    }
+
+    /// This function asks workshopper for the ids of all the items the user is subscribed to for the provided game.
+    pub fn subscribed_items(app: &AppHandle, game: &GameInfo) -> Result<Vec<String>> {
+        if !is_steam_running() {
+            return Err(anyhow!("Steam is not running."));
+        }
+
+        let settings = SETTINGS.read().unwrap().clone();
+        let game_path = settings.game_path(game)?;
+        let steam_id = game.steam_id(&game_path)? as u32;
+        let ipc_channel = rand::random::<u64>().to_string();
+
+        let command_string = format!(
+            "{} get-subscribed-items -s {steam_id} -i {ipc_channel} & exit",
+            &*WORKSHOPPER_PATH
+        );
+
+        let script_path = create_script(app, SCRIPT_GET_SUBSCRIBED_ITEMS, &command_string)?;
+        let mut command = workshopper_command(app, true, true, false)?;
+        command.arg(&script_path);
+        workshopper_command_post(&mut command, true, true, false);
+        command.spawn()?;
+
+        let channel = ipc_channel.to_ns_name::<GenericNamespaced>()?;
+        let server = ListenerOptions::new().name(channel).create_sync()?;
+        let mut stream = server.accept()?;
+
+        let mut message = String::new();
+        stream.read_to_string(&mut message)?;
+
+        let ids: Vec<u64> = serde_json::from_str(&message)
+            .map_err(|_| anyhow!("Error retrieving the subscribed items from Steam."))?;
+        Ok(ids.iter().map(|id| id.to_string()).collect())
+    }
+}
+
 //-------------------------------------------------------------------------------//
 //                      Utils used by this integration
 //-------------------------------------------------------------------------------//
@@ -544,6 +793,24 @@ fn create_script(app: &AppHandle, script_name: &str, command_string: &str) -> Re
     Ok(script_path)
 }
 
+/// Escapes a string so it can be used as the value of an html attribute.
+fn escape_html_attribute(value: &str) -> String {
+    value
+        .replace('&', "&amp;")
+        .replace('"', "&quot;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
+/// Turns the content of a BBCode list into html list items.
+fn list_items(content: &str) -> String {
+    content
+        .split("[*]")
+        .filter(|item| !item.trim().is_empty() && *item != "<br/>")
+        .map(|item| format!("<li>{}</li>", item.strip_prefix("<br/>").unwrap_or(item)))
+        .collect()
+}
+
 /// This function checks if Steam is running.
 fn is_steam_running() -> bool {
     let refresh_kind = sysinfo::RefreshKind::everything()
@@ -14,10 +14,11 @@ use rayon::prelude::*;
 use serde::{Deserialize, Serialize};
 use serde_json::to_string_pretty;
 
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::fs::{DirBuilder, File};
 use std::io::{BufReader, BufWriter, Read, Write};
 use std::path::{Path, PathBuf};
+use std::sync::atomic::Ordering;
 
 use rpfm_lib::binary::WriteBytes;
 use rpfm_lib::files::{Container, ContainerPath, pack::Pack};
@@ -25,9 +26,14 @@ use rpfm_lib::games::{GameInfo, pfh_file_type::PFHFileType, supported_games::*};
 use rpfm_lib::utils::{path_to_absolute_path, path_to_absolute_string};
 
 use crate::settings::{game_config_path, sql_scripts_extracted_path};
+use crate::{SETTINGS, UNSAVED_CHANGES};
 
 use super::game_config::GameConfig;
-use super::{SECONDARY_FOLDER_NAME, secondary_mods_path};
+use super::mods::ShareableMod;
+use super::{
+    SECONDARY_FOLDER_NAME, game_supports_content_loading, game_supports_custom_mod_list,
+    game_uses_movie_masking, secondary_mods_path,
+};
 
 const FILE_NAME_START: &str = "last_load_order_";
 const FILE_NAME_END: &str = ".json";
@@ -51,12 +57,24 @@ pub struct LoadOrder {
     // Id/Pack name of the mod. To get more data of the mod use this as key on the GameConfig/Mods hashmap.
     mods: Vec<String>,
 
-    // Movie Packs. These are not reorderable, so we keep them in a separate list.
+    // Movie Packs. These are only reorderable in games that don't mask them, so we keep them in a separate list.
     movies: Vec<String>,
 
+    // If the movie packs order is set by the user. Only used in games that don't mask movie packs.
+    #[serde(default)]
+    manual_movies: bool,
+
     // List of Packs open for data checking. Not serialized.
     #[serde(skip_deserializing, skip_serializing)]
     packs: HashMap<String, Pack>,
+
+    // Files extracted to the sql scripts folder, by path relative to that folder, and the mod they come from. Not serialized.
+    #[serde(skip_deserializing, skip_serializing)]
+    extracted_scripts: HashMap<String, String>,
+
+    // Extracted files shipped by more than one mod, with all the mods shipping them in load order. The last one wins. Not serialized.
+    #[serde(skip_deserializing, skip_serializing)]
+    script_conflicts: BTreeMap<String, Vec<String>>,
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -71,17 +89,113 @@ pub enum ImportedLoadOrderMode {
     Modlist(String),
 }
 
+/// Load order in a format that can be shared between users.
+#[derive(Clone, Debug, Default, Getters, MutGetters, Setters, Serialize, Deserialize)]
+#[getset(get = "pub", get_mut = "pub", set = "pub")]
+pub struct ShareableLoadOrder {
+    game_key: String,
+    mods: Vec<ShareableMod>,
+    movies: Vec<ShareableMod>,
+}
+
 //-------------------------------------------------------------------------------//
 //                             Implementations
 //-------------------------------------------------------------------------------//
 
+impl ImportedLoadOrderMode {
+    /// Detects if the provided text is a Runcher load order, or a raw mod list.
+    pub fn detect(data: String) -> Self {
+        if serde_json::from_str::<ShareableLoadOrder>(&data).is_ok() {
+            Self::Runcher(data)
+        } else {
+            Self::Modlist(data)
+        }
+    }
+
+    /// Returns the ids of the mods in the imported load order, in order.
+    ///
+    /// Modlists can be either mod_list.txt/user.script.txt files, or one pack name per line.
+    pub fn mod_ids(&self) -> Result<Vec<String>> {
+        match self {
+            Self::Runcher(data) => {
+                let load_order: ShareableLoadOrder = serde_json::from_str(data)?;
+                Ok(load_order
+                    .mods()
+                    .iter()
+                    .chain(load_order.movies().iter())
+                    .map(|modd| modd.id().to_owned())
+                    .collect())
+            }
+            Self::Modlist(data) => Ok(data
+                .lines()
+                .map(|line| line.trim())
+                .filter_map(|line| {
+                    let line = line.strip_prefix("mod ").unwrap_or(line).trim();
+                    let line = line.strip_suffix(';').unwrap_or(line).trim();
+                    let line = line.trim_matches('"');
+                    if line.ends_with(".pack") {
+                        Some(line.to_owned())
+                    } else {
+                        None
+                    }
+                })
+                .collect()),
+        }
+    }
+
+    /// Returns the hashes of the mods in the imported load order, by id. Only Runcher load orders have them.
+    pub fn mod_hashes(&self) -> Result<HashMap<String, String>> {
+        match self {
+            Self::Runcher(data) => {
+                let load_order: ShareableLoadOrder = serde_json::from_str(data)?;
+                Ok(load_order
+                    .mods()
+                    .iter()
+                    .chain(load_order.movies().iter())
+                    .filter(|modd| !modd.hash().is_empty())
+                    .map(|modd| (modd.id().to_owned(), modd.hash().to_owned()))
+                    .collect())
+            }
+            Self::Modlist(_) => Ok(HashMap::new()),
+        }
+    }
+}
+
+impl ShareableLoadOrder {
+    /// Builds a shareable version of the provided load order. Mods not found in the game config are skipped.
+    pub fn new(game: &GameInfo, load_order: &LoadOrder, game_config: &GameConfig) -> Self {
+        let shareable = |mod_ids: &[String]| {
+            mod_ids
+                .iter()
+                .filter_map(|mod_id| game_config.mods().get(mod_id))
+                .map(ShareableMod::from)
+                .collect::<Vec<_>>()
+        };
+
+        Self {
+            game_key: game.key().to_owned(),
+            mods: shareable(load_order.mods()),
+            movies: shareable(load_order.movies()),
+        }
+    }
+
+    pub fn save(&self, path: &Path) -> Result<()> {
+        let mut file = BufWriter::new(File::create(path)?);
+        file.write_all(to_string_pretty(&self)?.as_bytes())?;
+        Ok(())
+    }
+}
+
 impl Default for LoadOrder {
     fn default() -> Self {
         Self {
             automatic: true,
             mods: vec![],
             movies: vec![],
+            manual_movies: false,
             packs: HashMap::new(),
+            extracted_scripts: HashMap::new(),
+            script_conflicts: BTreeMap::new(),
         }
     }
 }
@@ -115,6 +229,16 @@ impl LoadOrder {
         Ok(())
     }
 
+    /// Saves the load order, unless autosave is disabled. In that case, it's just marked as having unsaved changes.
+    pub fn save_or_defer(&mut self, app_handle: &tauri::AppHandle, game: &GameInfo) -> Result<()> {
+        if SETTINGS.read().unwrap().autosave {
+            self.save(app_handle, game)
+        } else {
+            UNSAVED_CHANGES.store(true, Ordering::SeqCst);
+            Ok(())
+        }
+    }
+
     pub fn save_as_load_order_file(
         file_path: &Path,
         game: &GameInfo,
@@ -122,23 +246,56 @@ impl LoadOrder {
         pack_list: &str,
     ) -> anyhow::Result<()> {
         let mut file = BufWriter::new(File::create(file_path)?);
+        file.write_all(&Self::load_order_file_data(game, folder_list, pack_list)?)?;
+        file.flush().map_err(From::from)
+    }
+
+    /// Checks that the load order file on disk contains exactly what we intended to write to it.
+    ///
+    /// A full disk or permission issues may leave a truncated file, which would launch the game with half the mods.
+    pub fn verify_load_order_file(
+        file_path: &Path,
+        game: &GameInfo,
+        folder_list: &str,
+        pack_list: &str,
+    ) -> anyhow::Result<()> {
+        let expected = Self::load_order_file_data(game, folder_list, pack_list)?;
+        let written = std::fs::read(file_path)?;
+        if written != expected {
+            return Err(anyhow!(
+                "The load order file at {} doesn't match the load order ({} bytes written, {} expected). Check there's free space and you have permissions to write there.",
+                file_path.to_string_lossy(),
+                written.len(),
+                expected.len()
+            ));
+        }
+
+        Ok(())
+    }
+
+    fn load_order_file_data(
+        game: &GameInfo,
+        folder_list: &str,
+        pack_list: &str,
+    ) -> anyhow::Result<Vec<u8>> {
+        let mut data = vec![];
 
         // Napoleon, Empire and Shogun 2 require the user.script.txt or mod list file (for Shogun's latest update) to be in UTF-16 LE.
         if *game.raw_db_version() < 2 {
-            file.write_string_u16(folder_list)?;
-            file.write_string_u16(pack_list)?;
+            data.write_string_u16(folder_list)?;
+            data.write_string_u16(pack_list)?;
         } else {
-            file.write_all(folder_list.as_bytes())?;
-            file.write_all(pack_list.as_bytes())?;
+            data.write_all(folder_list.as_bytes())?;
+            data.write_all(pack_list.as_bytes())?;
         }
 
-        file.flush().map_err(From::from)
+        Ok(data)
     }
 
     pub fn path_as_load_order_file(game: &GameInfo, game_path: &Path) -> Result<PathBuf> {
         // NOTE: On Empire and Napoleon we need to use the user_script, not the custom file, as it doesn't seem to work.
         // Older versions of shogun 2 also used the user_script, but the latest update enabled use of custom mod lists.
-        if *game.raw_db_version() >= 1 {
+        if game_supports_custom_mod_list(game) {
             Ok(game_path.join(CUSTOM_MOD_LIST_FILE_NAME))
         } else {
             // Games may fail to launch if we don't have this path created, which is done the first time we start the game.
@@ -167,8 +324,6 @@ impl LoadOrder {
         game: &GameInfo,
         game_data_path: &Path,
     ) {
-        self.movies.clear();
-
         if self.automatic {
             self.build_automatic(game_config, game, game_data_path);
         } else {
@@ -193,13 +348,15 @@ impl LoadOrder {
             .collect();
 
         // Regenerate the extracted sql scripts and patches, based on the new load order.
+        self.extracted_scripts.clear();
+        self.script_conflicts.clear();
         if let Ok(sql_path) = sql_scripts_extracted_path(app_handle) {
             let _ = std::fs::remove_dir_all(&sql_path);
             let _ = DirBuilder::new().recursive(true).create(&sql_path);
 
             for mod_id in self.mods.iter().chain(self.movies.iter()) {
                 if let Some(pack) = self.packs.get_mut(mod_id) {
-                    let _ = pack.extract(
+                    let extracted = pack.extract(
                         ContainerPath::Folder("twpatcher/".to_string()),
                         &sql_path,
                         true,
@@ -209,6 +366,22 @@ impl LoadOrder {
                         &None,
                         false,
                     );
+
+                    // Keep track of where each file came from, so scripts can be traced back to their mod.
+                    for path in extracted.unwrap_or_default() {
+                        if let Some(key) = extracted_script_key(&sql_path, &path) {
+                            // If another mod already extracted this file, we just overwrote it.
+                            if let Some(previous) = self
+                                .extracted_scripts
+                                .insert(key.to_owned(), mod_id.to_owned())
+                            {
+                                self.script_conflicts
+                                    .entry(key)
+                                    .or_insert_with(|| vec![previous])
+                                    .push(mod_id.to_owned());
+                            }
+                        }
+                    }
                 }
             }
         }
@@ -293,8 +466,8 @@ impl LoadOrder {
     }
 
     fn build_movies(&mut self, game_config: &GameConfig, game: &GameInfo, game_data_path: &Path) {
-        // Movies are still automatic, even in manual mode.
-        self.movies = game_config
+        // Movies are still automatic, even in manual mode, unless the user reordered them.
+        let mut movies = game_config
             .mods()
             .values()
             .filter(|modd| {
@@ -306,7 +479,7 @@ impl LoadOrder {
             .collect::<Vec<_>>();
 
         // NOTE: The fallbacks are there because they're correct most of the time. But for Shogun 2 we NEED the pack comparison.
-        self.movies.sort_by(|a, b| {
+        movies.sort_by(|a, b| {
             let mod_a = game_config.mods().get(a);
             let mod_b = game_config.mods().get(b);
             if let Some(mod_a) = mod_a {
@@ -323,6 +496,18 @@ impl LoadOrder {
                 a.cmp(b)
             }
         });
+
+        // Games that mask movie packs load them from /data in their own order, so only the others can use a manual order.
+        if self.manual_movies && !game_uses_movie_masking(game) {
+            self.movies.retain(|mod_id| movies.contains(mod_id));
+            for mod_id in movies {
+                if !self.movies.contains(&mod_id) {
+                    self.movies.push(mod_id);
+                }
+            }
+        } else {
+            self.movies = movies;
+        }
     }
 
     pub fn build_load_order_string(
@@ -333,14 +518,21 @@ impl LoadOrder {
         game_data_path: &Path,
         pack_string: &mut String,
         folder_paths: &mut String,
-    ) {
+    ) -> Result<()> {
         let mut added_secondary_folder = false;
         let secondary_mods_path =
             secondary_mods_path(app_handle, game.key()).unwrap_or_else(|_| PathBuf::new());
         let secondary_mods_masks_path =
             path_to_absolute_path(&secondary_mods_path.join(SECONDARY_FOLDER_NAME), true);
-        let game_data_path = game_data_path.canonicalize().unwrap();
+        let game_data_path = game_data_path.canonicalize().map_err(|error| {
+            anyhow!(
+                "The game's data folder ({}) could not be found. Check the game's path in the settings. Error: {}",
+                game_data_path.to_string_lossy(),
+                error
+            )
+        })?;
         let mut folder_paths_mods = String::new();
+        let mut added_folders = HashSet::new();
 
         for mod_id in self.mods() {
             self.process_mod(
@@ -351,6 +543,7 @@ impl LoadOrder {
                 &mut folder_paths_mods,
                 mod_id,
                 &mut added_secondary_folder,
+                &mut added_folders,
                 &secondary_mods_path,
                 &secondary_mods_masks_path,
             );
@@ -366,6 +559,7 @@ impl LoadOrder {
                 &mut folder_paths_mods,
                 mod_id,
                 &mut added_secondary_folder,
+                &mut added_folders,
                 &secondary_mods_path,
                 &secondary_mods_masks_path,
             );
@@ -377,11 +571,7 @@ impl LoadOrder {
         for modd in game_config.mods().values() {
             if !modd.enabled(game, &game_data_path) && *modd.pack_type() == PFHFileType::Movie {
                 // This only works for Warhammer I and later games.
-                if *game.raw_db_version() >= 2
-                    && (game.key() != KEY_ROME_2
-                        && game.key() != KEY_ATTILA
-                        && game.key() != KEY_THRONES_OF_BRITANNIA)
-                {
+                if !game_uses_movie_masking(game) {
                     if let Some(path) = modd.paths().first() {
                         let pack_name = path
                             .file_name()
@@ -395,7 +585,7 @@ impl LoadOrder {
 
                         // If it's the secondary folder and we're using it for another pack, or it's in data, add an exclusion for it.
                         if (secondary_mods_path.is_dir()
-                            && folder_path == secondary_mods_path
+                            && is_same_folder(&folder_path, &secondary_mods_path)
                             && added_secondary_folder)
                             || path.starts_with(&game_data_path)
                         {
@@ -408,6 +598,8 @@ impl LoadOrder {
         }
 
         folder_paths.push_str(&folder_paths_mods);
+
+        Ok(())
     }
 
     pub fn move_mod_in_direction(&mut self, mod_id: &str, direction: LoadOrderDirectionMove) {
@@ -447,6 +639,167 @@ impl LoadOrder {
         }
     }
 
+    /// Moves a movie pack above another one, switching the movie packs to manual order.
+    pub fn move_movie_above_another(&mut self, source_id: &str, target_id: &str) {
+        if source_id == target_id {
+            return;
+        }
+
+        self.manual_movies = true;
+        if let Some(index) = self.movies.iter().position(|id| id == source_id) {
+            if let Some(mut index_target) = self.movies.iter().position(|id| id == target_id) {
+                // Compensate for the index shift after removing the source mod.
+                if index_target > index {
+                    index_target -= 1;
+                }
+
+                self.movies.remove(index);
+                self.movies.insert(index_target, source_id.to_string());
+            }
+        }
+    }
+
+    /// Replaces a mod id in the load order, keeping its position.
+    pub fn rename_mod(&mut self, old_id: &str, new_id: &str) {
+        self.mods
+            .iter_mut()
+            .chain(self.movies.iter_mut())
+            .filter(|id| *id == old_id)
+            .for_each(|id| *id = new_id.to_owned());
+
+        if let Some(pack) = self.packs.remove(old_id) {
+            self.packs.insert(new_id.to_owned(), pack);
+        }
+    }
+
+    /// Replaces any entry using the pack name of a legacy mod with the id of said mod, as after converting them to packs
+    /// they're still tracked under their bin id. Returns the replaced entries, as (old id, new id).
+    pub fn migrate_bin_names(&mut self, game_config: &GameConfig) -> Vec<(String, String)> {
+        let mut migrated = vec![];
+        for modd in game_config.mods().values() {
+            if let Some(alt_name) = modd.alt_name() {
+                if &alt_name == modd.id()
+                    || (!self.mods.contains(&alt_name) && !self.movies.contains(&alt_name))
+                {
+                    continue;
+                }
+
+                // If the mod is already in the list under its id, the pack name entry is just a leftover.
+                if self.mods.contains(modd.id()) || self.movies.contains(modd.id()) {
+                    self.mods.retain(|id| id != &alt_name);
+                    self.movies.retain(|id| id != &alt_name);
+                    self.packs.remove(&alt_name);
+                } else {
+                    self.rename_mod(&alt_name, modd.id());
+                }
+
+                migrated.push((alt_name, modd.id().to_owned()));
+            }
+        }
+
+        migrated
+    }
+
+    /// Returns the files and DB tables shipped by more than one loaded pack, with all the packs shipping them in load order.
+    ///
+    /// Packs earlier in the list take priority, so the first one wins. Movie packs override the mods, so they go first.
+    /// Table conflicts are per table, not per row, as we need a schema to decode the rows.
+    pub fn pack_conflicts(&self) -> (BTreeMap<String, Vec<String>>, BTreeMap<String, Vec<String>>) {
+        let mut files: BTreeMap<String, Vec<String>> = BTreeMap::new();
+        let mut tables: BTreeMap<String, Vec<String>> = BTreeMap::new();
+
+        for mod_id in self.movies.iter().chain(self.mods.iter()) {
+            if let Some(pack) = self.packs.get(mod_id) {
+                let mut pack_tables = HashSet::new();
+                for path in pack.files().keys() {
+                    // Paths are case-insensitive in game.
+                    let path = path.to_lowercase();
+                    if let Some(table_name) = path
+                        .strip_prefix("db/")
+                        .and_then(|path| path.split('/').next())
+                    {
+                        pack_tables.insert(table_name.to_owned());
+                    }
+
+                    files.entry(path).or_default().push(mod_id.to_owned());
+                }
+
+                for table_name in pack_tables {
+                    tables
+                        .entry(table_name)
+                        .or_default()
+                        .push(mod_id.to_owned());
+                }
+            }
+        }
+
+        files.retain(|_, mods| mods.len() > 1);
+        tables.retain(|_, mods| mods.len() > 1);
+
+        (files, tables)
+    }
+
+    /// Returns a suggested order for the loaded mods. Movie packs are not included, as they have their own order.
+    ///
+    /// As packs earlier in the list take priority, mods go before the mods they depend on, so submods can override their parents.
+    /// Otherwise, mods with fewer files go first, as they tend to be more specific and are meant to override broader ones.
+    /// Ties, and mods with circular dependencies, keep their current relative order.
+    pub fn suggested_order(&self) -> Vec<String> {
+        let file_count = |mod_id: &str| {
+            self.packs
+                .get(mod_id)
+                .map(|pack| pack.files().len())
+                .unwrap_or_default()
+        };
+
+        // Mods that need to go after each mod, as it depends on them.
+        let mut dependencies: HashMap<&str, HashSet<&str>> = HashMap::new();
+        let mut pending_dependants: HashMap<&str, usize> = HashMap::new();
+        for mod_id in &self.mods {
+            if let Some(pack) = self.packs.get(mod_id) {
+                for (_, dependency) in pack.dependencies() {
+                    if dependency != mod_id && self.mods.contains(dependency) {
+                        if dependencies
+                            .entry(mod_id.as_str())
+                            .or_default()
+                            .insert(dependency.as_str())
+                        {
+                            *pending_dependants.entry(dependency.as_str()).or_default() += 1;
+                        }
+                    }
+                }
+            }
+        }
+
+        let mut remaining = self.mods.iter().map(|id| id.as_str()).collect::<Vec<_>>();
+        let mut order = Vec::with_capacity(remaining.len());
+        while !remaining.is_empty() {
+            let index = remaining
+                .iter()
+                .enumerate()
+                .filter(|(_, id)| pending_dependants.get(*id).copied().unwrap_or_default() == 0)
+                .min_by_key(|(index, id)| (file_count(id), *index))
+                .map(|(index, _)| index);
+
+            // If every remaining mod is waiting for another one, there's a dependency cycle. Leave them as they are.
+            let Some(index) = index else {
+                order.extend(remaining.iter().map(|id| id.to_string()));
+                break;
+            };
+
+            let mod_id = remaining.remove(index);
+            for dependency in dependencies.get(mod_id).into_iter().flatten() {
+                if let Some(count) = pending_dependants.get_mut(dependency) {
+                    *count -= 1;
+                }
+            }
+
+            order.push(mod_id.to_owned());
+        }
+
+        order
+    }
+
     fn process_mod(
         &self,
         game_config: &GameConfig,
@@ -456,6 +809,7 @@ impl LoadOrder {
         folder_paths: &mut String,
         mod_id: &str,
         added_secondary_folder: &mut bool,
+        added_folders: &mut HashSet<String>,
         secondary_mods_path: &PathBuf,
         secondary_mods_masks_path: &PathBuf,
     ) {
@@ -474,13 +828,15 @@ impl LoadOrder {
                     .to_string_lossy()
                     .as_ref()
                     .to_owned();
-                if !path.starts_with(game_data_path) && *game.raw_db_version() >= 1 {
+                if !path.starts_with(game_data_path) && game_supports_content_loading(game) {
                     let mut folder_path = path_to_absolute_path(path, false);
                     folder_path.pop();
 
                     // If it's the secondary folder, just add it once. If it's the contents folder, add one per mod.
-                    let folder_path_str = path_to_absolute_string(&folder_path);
-                    if secondary_mods_path.is_dir() && folder_path == *secondary_mods_path {
+                    let folder_path_str = load_order_path_string(&folder_path);
+                    if secondary_mods_path.is_dir()
+                        && is_same_folder(&folder_path, secondary_mods_path)
+                    {
                         if !*added_secondary_folder {
                             folder_paths.insert_str(
                                 0,
@@ -488,24 +844,21 @@ impl LoadOrder {
                             );
 
                             // This is only needed for games relying on masking movie packs.
-                            if *game.raw_db_version() <= 1
-                                || (*game.raw_db_version() == 2
-                                    && (game.key() == KEY_ROME_2
-                                        || game.key() == KEY_ATTILA
-                                        || game.key() == KEY_THRONES_OF_BRITANNIA))
-                            {
+                            if game_uses_movie_masking(game) {
                                 folder_paths.insert_str(
                                     0,
                                     &format!(
                                         "add_working_directory \"{}\";\n",
-                                        secondary_mods_masks_path.to_string_lossy()
+                                        load_order_path_string(secondary_mods_masks_path)
                                     ),
                                 );
                             }
 
                             *added_secondary_folder = true;
                         }
-                    } else {
+                    }
+                    // Many mods can share a folder, so make sure we only add each folder once.
+                    else if added_folders.insert(folder_path_str.to_owned()) {
                         folder_paths
                             .push_str(&format!("add_working_directory \"{}\";\n", folder_path_str));
                     }
@@ -523,3 +876,26 @@ impl LoadOrder {
         }
     }
 }
+
+/// Returns the key used to track an extracted sql script: its path relative to the extracted scripts folder, with forward slashes.
+pub fn extracted_script_key(sql_path: &Path, path: &Path) -> Option<String> {
+    path.strip_prefix(sql_path)
+        .ok()
+        .map(|path| path.to_string_lossy().replace('\\', "/"))
+}
+
+/// Function to turn a path into the format used in the load order file and the launch commands.
+///
+/// The games accept forward slashes on every platform, so we always use them. That way the same setup generates the same file everywhere.
+pub fn load_order_path_string(path: &Path) -> String {
+    path_to_absolute_string(path).replace('\\', "/")
+}
+
+/// Function to check if two paths point to the same folder, resolving symlinks if possible.
+///
+/// The secondary folder path is canonicalized, so comparing it directly with non-canonical paths fails when it's behind a symlink.
+fn is_same_folder(path: &Path, other: &Path) -> bool {
+    let path = path.canonicalize().unwrap_or_else(|_| path.to_path_buf());
+    let other = other.canonicalize().unwrap_or_else(|_| other.to_path_buf());
+    path == other
+}
@@ -18,20 +18,21 @@ use regex::Regex;
 use std::cell::LazyCell;
 use std::collections::HashMap;
 use std::fs::DirBuilder;
-use std::io::Cursor;
+use std::io::{Cursor, Read};
 use std::path::{Path, PathBuf};
 
 use rpfm_lib::binary::ReadBytes;
 use rpfm_lib::files::{
     Container, FileType, RFile, RFileDecoded, db::DB, loc::Loc, pack::Pack, table::DecodedData,
 };
-use rpfm_lib::games::{GameInfo, pfh_file_type::PFHFileType, supported_games::SupportedGames};
+use rpfm_lib::games::{GameInfo, pfh_file_type::PFHFileType, supported_games::*};
 use rpfm_lib::utils::{files_from_subdir, path_to_absolute_path, path_to_absolute_string};
 
 use crate::SCHEMA;
 use crate::settings::AppSettings;
 
 use self::game_config::GameConfig;
+use self::load_order::LoadOrder;
 use self::mods::Mod;
 
 pub mod game_config;
@@ -40,6 +41,7 @@ pub mod load_order;
 pub mod mods;
 pub mod profiles;
 pub mod saves;
+pub mod snapshots;
 
 const REGEX_MAP_INFO_DISPLAY_NAME: LazyCell<Regex> =
     LazyCell::new(|| Regex::new(r"<display_name>(.*)</display_name>").unwrap());
@@ -141,7 +143,7 @@ pub fn move_to_secondary(
 
     let settings = AppSettings::load(app_handle)?;
     let game_path = settings.game_path(game)?;
-    let secondary_path = settings.secondary_mods_path()?;
+    let secondary_path = secondary_mods_path(app_handle, game.key())?;
     let data_path = game.data_path(&game_path)?;
     let data_path_str = path_to_absolute_string(&data_path);
 
@@ -155,7 +157,10 @@ pub fn move_to_secondary(
                 .collect::<Vec<_>>();
 
             // If the first path is /data, proceed. If not, we cannot move this mod.
-            if decannon_paths[0].starts_with(&data_path_str) {
+            if decannon_paths
+                .first()
+                .is_some_and(|path| path.starts_with(&data_path_str))
+            {
                 let new_path = secondary_path.join(modd.paths()[0].file_name().unwrap());
                 if std::fs::copy(&modd.paths()[0], new_path).is_err() {
                     mods_failed.push(modd.id().to_string());
@@ -185,6 +190,58 @@ pub fn move_to_secondary(
     Ok(mods_failed)
 }
 
+pub fn copy_to_data(
+    app_handle: &tauri::AppHandle,
+    game: &GameInfo,
+    game_config: &GameConfig,
+    mod_ids: &[String],
+) -> Result<Vec<String>> {
+    let mut mods_failed = vec![];
+
+    let settings = AppSettings::load(app_handle)?;
+    let game_path = settings.game_path(game)?;
+    let data_path = game.data_path(&game_path)?;
+    let data_path_str = path_to_absolute_string(&data_path);
+
+    for mod_id in mod_ids {
+        if let Some(modd) = game_config.mods().get(mod_id) {
+            let decannon_paths = modd
+                .paths()
+                .iter()
+                .map(|path| path_to_absolute_string(path))
+                .collect::<Vec<_>>();
+
+            // Apply only to mods in secondary or content that are not already in /data.
+            if !decannon_paths.is_empty()
+                && decannon_paths
+                    .iter()
+                    .all(|path| !path.starts_with(&data_path_str))
+            {
+                let new_path = data_path.join(modd.paths()[0].file_name().unwrap());
+                if new_path.exists() || std::fs::copy(&modd.paths()[0], &new_path).is_err() {
+                    mods_failed.push(modd.id().to_string());
+                }
+                // Copy the png too.
+                else {
+                    let mut old_image_path = PathBuf::from(&decannon_paths[0]);
+                    old_image_path.set_extension("png");
+
+                    let mut new_image_path = new_path;
+                    new_image_path.set_extension("png");
+
+                    let _ = std::fs::copy(&old_image_path, &new_image_path);
+                }
+            }
+            // Any other case is not supported.
+            else {
+                mods_failed.push(modd.id().to_string());
+            }
+        }
+    }
+
+    Ok(mods_failed)
+}
+
 /// Function to move files from /content to /secondary, or /data.
 fn move_to_destination(
     data_path: &Path,
@@ -245,10 +302,154 @@ fn move_to_destination(
     Ok(())
 }
 
+/// Function to make sure the folders the game needs to load mods exist, as some of them are only created on first launch.
+///
+/// This includes /data and, for games using user scripts, the config scripts folder.
+pub fn ensure_game_folders(game: &GameInfo, game_path: &Path) -> Result<()> {
+    // Do not create folders on a wrong path, or we'll hide the misconfiguration.
+    if !game
+        .executable_path(game_path)
+        .is_some_and(|path| path.is_file())
+    {
+        return Err(anyhow!(
+            "The game's executable could not be found. Check the game's path in the settings."
+        ));
+    }
+
+    let data_path = game.data_path(game_path)?;
+    DirBuilder::new()
+        .recursive(true)
+        .create(&data_path)
+        .map_err(|e| anyhow!("Error creating the data path: {}", e))?;
+
+    // Games using user scripts create the scripts path too, so just get the file path.
+    LoadOrder::path_as_load_order_file(game, game_path)?;
+
+    Ok(())
+}
+
+/// Function to know if a game disables movie packs by masking them with empty packs.
+///
+/// The exclude_pack_file command is only supported since Warhammer I, so older games need to mask them instead.
+pub fn game_uses_movie_masking(game: &GameInfo) -> bool {
+    *game.raw_db_version() <= 1
+        || (*game.raw_db_version() == 2
+            && (game.key() == KEY_ROME_2
+                || game.key() == KEY_ATTILA
+                || game.key() == KEY_THRONES_OF_BRITANNIA))
+}
+
+/// Function to get the file names of the disabled movie packs in the secondary folder, which need masking on launch.
+pub fn movies_to_mask(
+    game: &GameInfo,
+    game_config: &GameConfig,
+    data_path: &Path,
+    secondary_mods_path: &Path,
+) -> Result<Vec<String>> {
+    let mut file_names = vec![];
+    for path in std::fs::read_dir(secondary_mods_path)? {
+        let file_name = path?.file_name().to_string_lossy().to_string();
+
+        if let Some(modd) = game_config.mods().get(&file_name) {
+            if modd.pack_type() == &PFHFileType::Movie && !modd.enabled(game, data_path) {
+                file_names.push(file_name);
+            }
+        }
+    }
+
+    file_names.sort();
+    Ok(file_names)
+}
+
+/// Function to mask all disabled movie packs in the secondary folder with empty packs, in its masks folder.
+///
+/// Masks are generated in a temp folder and swapped in at the end, so a failure never leaves a half-populated masks folder.
+pub fn generate_movie_masks(
+    game: &GameInfo,
+    game_config: &GameConfig,
+    data_path: &Path,
+    secondary_mods_path: &Path,
+) -> Result<()> {
+    let masks_path = secondary_mods_path.join(SECONDARY_FOLDER_NAME);
+    let masks_tmp_path = secondary_mods_path.join(format!("{SECONDARY_FOLDER_NAME}_tmp"));
+    let masks_old_path = secondary_mods_path.join(format!("{SECONDARY_FOLDER_NAME}_old"));
+
+    // Remove leftovers from previous failed runs.
+    for path in [&masks_tmp_path, &masks_old_path] {
+        if path.is_dir() {
+            std::fs::remove_dir_all(path)?;
+        }
+    }
+
+    DirBuilder::new().recursive(true).create(&masks_tmp_path)?;
+
+    let mut mask_pack = Pack::new_with_version(game.pfh_version_by_file_type(PFHFileType::Movie));
+    mask_pack.set_pfh_file_type(PFHFileType::Movie);
+
+    let file_names = movies_to_mask(game, game_config, data_path, secondary_mods_path);
+    let result = file_names.and_then(|file_names| {
+        for file_name in file_names {
+            mask_pack.save(Some(&masks_tmp_path.join(file_name)), game, &None)?;
+        }
+
+        Ok(())
+    });
+
+    if let Err(error) = result {
+        let _ = std::fs::remove_dir_all(&masks_tmp_path);
+        return Err(error);
+    }
+
+    // Swap the folders. If the old one is in use this fails, and we keep the old masks in place.
+    if masks_path.is_dir() {
+        if let Err(error) = std::fs::rename(&masks_path, &masks_old_path) {
+            let _ = std::fs::remove_dir_all(&masks_tmp_path);
+            return Err(anyhow!(
+                "Error replacing the masks folder. Is the game still running? {}",
+                error
+            ));
+        }
+    }
+
+    // If the new masks cannot be moved in place, put the old ones back so the game isn't left without masks.
+    if let Err(error) = std::fs::rename(&masks_tmp_path, &masks_path) {
+        if masks_old_path.is_dir() {
+            let _ = std::fs::rename(&masks_old_path, &masks_path);
+        }
+
+        let _ = std::fs::remove_dir_all(&masks_tmp_path);
+        return Err(error.into());
+    }
+
+    let _ = std::fs::remove_dir_all(&masks_old_path);
+
+    Ok(())
+}
+
+/// Function to know if a game can load mods from a secondary folder. Empire and Napoleon can't.
+pub fn game_supports_secondary_folder(game: &GameInfo) -> bool {
+    *game.raw_db_version() >= 1
+}
+
+/// Function to know if a game can load mods from their content folders through add_working_directory.
+pub fn game_supports_content_loading(game: &GameInfo) -> bool {
+    *game.raw_db_version() >= 1
+}
+
+/// Function to know if a game can use a custom mod list file instead of the user script. Only Shogun 2 and later games can.
+pub fn game_supports_custom_mod_list(game: &GameInfo) -> bool {
+    *game.raw_db_version() >= 1
+}
+
+/// Function to know if a game accepts extra arguments in the launch command. Only Shogun 2 and later games do.
+pub fn game_supports_extra_launch_args(game: &GameInfo) -> bool {
+    *game.raw_db_version() >= 1
+}
+
 pub fn secondary_mods_path(app_handle: &tauri::AppHandle, game: &str) -> Result<PathBuf> {
     match SupportedGames::default().game(game) {
         Some(game_info) => {
-            if game_info.raw_db_version() < &1 {
+            if !game_supports_secondary_folder(game_info) {
                 return Err(anyhow!(
                     "This game ({}) doesn't support secondary mod folders.",
                     game
@@ -258,15 +459,23 @@ pub fn secondary_mods_path(app_handle: &tauri::AppHandle, game: &str) -> Result<
         None => return Err(anyhow!("What kind of game is {}?", game)),
     }
 
+    // Games may have their own secondary folder, in a different place than the global one.
     let settings = AppSettings::load(app_handle)?;
-    let base_path = settings.secondary_mods_path()?;
+    let base_path = settings.secondary_mods_path_for_game(game)?;
     let base_path_str = path_to_absolute_string(&base_path);
     if base_path_str.is_empty() {
         return Err(anyhow!("Secondary Mods Path not set."));
     }
 
     // Canonicalization is required due to some issues with the game not loading not properly formatted paths.
-    let path = std::fs::canonicalize(PathBuf::from(base_path_str))?;
+    let path = std::fs::canonicalize(PathBuf::from(&base_path_str)).map_err(|error| {
+        anyhow!(
+            "Secondary Mods Path for {} ({}) is not valid: {}",
+            game,
+            base_path_str,
+            error
+        )
+    })?;
     let game_path = path.join(game);
 
     if !path.is_dir() {
@@ -280,6 +489,32 @@ pub fn secondary_mods_path(app_handle: &tauri::AppHandle, game: &str) -> Result<
     Ok(game_path)
 }
 
+/// Function to know if two existing paths are on the same volume. Uses the drive prefix on Windows, and the device id on Linux.
+pub fn is_same_volume(path_a: &Path, path_b: &Path) -> Result<bool> {
+    #[cfg(target_os = "windows")]
+    {
+        use std::path::Component;
+
+        let prefix = |path: &Path| -> Result<String> {
+            match std::fs::canonicalize(path)?.components().next() {
+                Some(Component::Prefix(prefix)) => {
+                    Ok(prefix.as_os_str().to_string_lossy().to_lowercase())
+                }
+                _ => Err(anyhow!("Path {} has no drive.", path.to_string_lossy())),
+            }
+        };
+
+        Ok(prefix(path_a)? == prefix(path_b)?)
+    }
+
+    #[cfg(any(target_os = "linux", target_os = "macos"))]
+    {
+        use std::os::unix::fs::MetadataExt;
+
+        Ok(path_a.metadata()?.dev() == path_b.metadata()?.dev())
+    }
+}
+
 pub fn secondary_mods_packs_paths(
     app_handle: &tauri::AppHandle,
     game: &str,
@@ -303,6 +538,75 @@ pub fn secondary_mods_packs_paths(
     Some(paths)
 }
 
+/// Function to regenerate the pack of a Shogun 2 map mod from its bin, overwriting the previous conversion if there is one.
+///
+/// Used to fix maps converted while the schema wasn't loaded, which lack their battles and loc tables. Returns the path of the pack.
+pub fn regenerate_map_pack(
+    app_handle: &tauri::AppHandle,
+    game: &GameInfo,
+    game_path: &Path,
+    modd: &mut Mod,
+) -> Result<PathBuf> {
+    if game.key() != KEY_SHOGUN_2 {
+        return Err(anyhow!("Only Shogun 2 has map mods."));
+    }
+
+    if SCHEMA.read().unwrap().is_none() {
+        return Err(anyhow!(
+            "No schema loaded. The map would be generated without its tables."
+        ));
+    }
+
+    if !modd.is_map_mod() {
+        return Err(anyhow!("Mod {} is not a map mod.", modd.id()));
+    }
+
+    // Maps only contain a folder name. We need to change it into a pack name.
+    let name = modd
+        .file_name()
+        .split('/')
+        .last()
+        .unwrap_or_default()
+        .replace(" ", "_");
+    let pack_name = name.to_owned() + ".pack";
+
+    let bin_path = modd
+        .paths()
+        .iter()
+        .find(|path| path.extension().is_some_and(|ext| ext == "bin"))
+        .ok_or_else(|| anyhow!("The bin of mod {} could not be found.", modd.id()))?;
+
+    let data = std::fs::read(bin_path)?;
+    let mut decompressor = flate2::read::ZlibDecoder::new(Cursor::new(data));
+    let mut data_dec = vec![];
+    decompressor.read_to_end(&mut data_dec)?;
+
+    let mut pack = generate_map_pack(game, &data_dec, &pack_name, &name)?;
+
+    // Overwrite the previous conversion if we have one. If not, put it where a new conversion would go.
+    let data_path = game.data_path(game_path)?;
+    let pack_path = match modd.paths().iter().find(|path| {
+        path.file_name()
+            .is_some_and(|file_name| file_name == pack_name.as_str())
+    }) {
+        Some(path) => path.to_path_buf(),
+        None => match secondary_mods_path(app_handle, game.key()) {
+            Ok(secondary_path) if !data_path.join(&pack_name).is_file() => {
+                secondary_path.join(&pack_name)
+            }
+            _ => data_path.join(&pack_name),
+        },
+    };
+
+    pack.save(Some(&pack_path), game, &None)?;
+
+    if !modd.paths().contains(&pack_path) {
+        modd.paths_mut().insert(0, pack_path.to_path_buf());
+    }
+
+    Ok(pack_path)
+}
+
 /// Function to generate a pack from a Shogun 2 map bin data.
 fn generate_map_pack(
     game: &GameInfo,
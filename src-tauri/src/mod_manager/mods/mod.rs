@@ -14,14 +14,12 @@ use serde::{Deserialize, Serialize};
 use sha256::try_digest;
 
 use std::path::{Path, PathBuf};
+use std::time::SystemTime;
 
-use rpfm_lib::games::{
-    GameInfo,
-    pfh_file_type::PFHFileType,
-    supported_games::{KEY_ATTILA, KEY_ROME_2, KEY_THRONES_OF_BRITANNIA},
-};
+use rpfm_lib::games::{GameInfo, pfh_file_type::PFHFileType};
 use rpfm_lib::utils::{path_to_absolute_path, path_to_absolute_string};
 
+use super::game_uses_movie_masking;
 use super::integrations::StoreId;
 
 //pub mod versions;
@@ -58,6 +56,10 @@ pub struct Mod {
     /// Nick of the creator/owner of the mod.
     creator_name: String,
 
+    /// Creator name set by the user. Only used for mods not uploaded to any store, as they have no creator name.
+    #[serde(default)]
+    creator_name_override: Option<String>,
+
     /// File name. If present, it's the name we need to give to the file when converting from bin to pack.
     ///
     /// Only present in old games for .bin files. Used as name when moving a .bin file to /data.
@@ -77,6 +79,20 @@ pub struct Mod {
 
     /// Time the mod was last updated on the workshop.
     time_updated: usize,
+
+    /// Location the user wants this mod to be loaded from, if it's in more than one place.
+    #[serde(default)]
+    preferred_location: Option<ModLocation>,
+}
+
+/// Where a specific path of a mod lives.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ModLocation {
+    Data,
+    Secondary,
+    Content,
+    Unknown,
 }
 
 #[derive(Clone, Debug, Default, Getters, MutGetters, Setters, Serialize, Deserialize)]
@@ -94,7 +110,12 @@ pub struct ShareableMod {
 
 impl From<&Mod> for ShareableMod {
     fn from(value: &Mod) -> Self {
-        let hash = try_digest(value.paths()[0].as_path()).unwrap();
+        // Mods without paths, or with unreadable ones, have no hash to compare against.
+        let hash = value
+            .paths()
+            .first()
+            .and_then(|path| try_digest(path.as_path()).ok())
+            .unwrap_or_default();
         Self {
             name: value.name().to_owned(),
             id: value.id().to_owned(),
@@ -139,6 +160,44 @@ impl Mod {
         (data, secondary, content)
     }
 
+    /// Returns where the provided path is, using the same rules as [Mod::location].
+    pub fn path_location(
+        path: &Path,
+        data_path: &str,
+        secondary_path: &str,
+        content_path: &str,
+    ) -> ModLocation {
+        let path = path_to_absolute_string(path);
+        if path.starts_with(data_path) {
+            ModLocation::Data
+        } else if !secondary_path.is_empty() && path.starts_with(secondary_path) {
+            ModLocation::Secondary
+        } else if !content_path.is_empty() && path.starts_with(content_path) {
+            ModLocation::Content
+        } else {
+            ModLocation::Unknown
+        }
+    }
+
+    /// Moves the first path in the preferred location, if any, to the front so it becomes the active one.
+    pub fn apply_preferred_location(
+        &mut self,
+        data_path: &str,
+        secondary_path: &str,
+        content_path: &str,
+    ) {
+        if let Some(preferred) = self.preferred_location {
+            let index = self.paths.iter().position(|path| {
+                Self::path_location(path, data_path, secondary_path, content_path) == preferred
+            });
+
+            if let Some(index) = index {
+                let path = self.paths.remove(index);
+                self.paths.insert(0, path);
+            }
+        }
+    }
+
     pub fn priority_dating_flags(
         &self,
         data_path: &str,
@@ -150,55 +209,25 @@ impl Mod {
             return Ok((false, false, false));
         }
 
-        let mut data_older_than_secondary = false;
-        let mut data_older_than_content = false;
-        let mut secondary_older_than_content = false;
-
-        let paths = self
-            .paths()
-            .iter()
-            .map(|x| path_to_absolute_string(x))
-            .collect::<Vec<_>>();
-
-        if paths.len() == 2 {
-            let date_0 = PathBuf::from(&paths[0]).metadata()?.modified()?;
-            let date_1 = PathBuf::from(&paths[1]).metadata()?.modified()?;
-
-            if date_1 > date_0 {
-                if paths[0].starts_with(data_path) {
-                    if !secondary_path.is_empty() && paths[1].starts_with(secondary_path) {
-                        data_older_than_secondary = true;
-                    } else if !content_path.is_empty() && paths[1].starts_with(content_path) {
-                        data_older_than_content = true;
-                    }
-                } else if !secondary_path.is_empty() && paths[0].starts_with(secondary_path) {
-                    secondary_older_than_content = true;
-                }
-            }
-        }
-
-        if paths.len() == 3 {
-            let date_0 = PathBuf::from(&paths[0]).metadata()?.modified()?;
-            let date_1 = PathBuf::from(&paths[1]).metadata()?.modified()?;
-            let date_2 = PathBuf::from(&paths[2]).metadata()?.modified()?;
-
-            if date_1 > date_0 {
-                data_older_than_secondary = true;
-            }
-
-            if date_2 > date_0 {
-                data_older_than_content = true;
+        // The preferred location may have moved any path to the front, so find each path by its location, not its position.
+        let modified = |location: ModLocation| -> Result<Option<SystemTime>> {
+            match self.paths().iter().find(|path| {
+                Self::path_location(path, data_path, secondary_path, content_path) == location
+            }) {
+                Some(path) => Ok(Some(path.metadata()?.modified()?)),
+                None => Ok(None),
             }
+        };
 
-            if date_2 > date_1 {
-                secondary_older_than_content = true;
-            }
-        }
+        let data = modified(ModLocation::Data)?;
+        let secondary = modified(ModLocation::Secondary)?;
+        let content = modified(ModLocation::Content)?;
+        let older = |a: Option<SystemTime>, b: Option<SystemTime>| matches!((a, b), (Some(a), Some(b)) if b > a);
 
         Ok((
-            data_older_than_secondary,
-            data_older_than_content,
-            secondary_older_than_content,
+            older(data, secondary),
+            older(data, content),
+            older(secondary, content),
         ))
     }
 
@@ -214,11 +243,7 @@ impl Mod {
         if self.pack_type == PFHFileType::Mod {
             self.enabled
         } else if self.pack_type == PFHFileType::Movie {
-            if *game.raw_db_version() >= 2
-                && (game.key() != KEY_ROME_2
-                    && game.key() != KEY_ATTILA
-                    && game.key() != KEY_THRONES_OF_BRITANNIA)
-            {
+            if !game_uses_movie_masking(game) {
                 self.enabled
             } else if let Some(path) = self.paths().first() {
                 if path.starts_with(&data_path) {
@@ -241,6 +266,18 @@ impl Mod {
         self.enabled = enabled;
     }
 
+    /// Copies the data retrieved from the store from another copy of the same mod.
+    pub fn copy_online_data(&mut self, other: &Mod) {
+        self.name = other.name.clone();
+        self.creator = other.creator.clone();
+        self.creator_name = other.creator_name.clone();
+        self.file_name = other.file_name.clone();
+        self.file_size = other.file_size;
+        self.description = other.description.clone();
+        self.time_created = other.time_created;
+        self.time_updated = other.time_updated;
+    }
+
     pub fn can_be_toggled(&self, game: &GameInfo, data_path: &Path) -> bool {
         let data_path = path_to_absolute_path(data_path, false);
 
@@ -248,11 +285,7 @@ impl Mod {
         if self.pack_type == PFHFileType::Mod {
             true
         } else if self.pack_type == PFHFileType::Movie {
-            if *game.raw_db_version() >= 2
-                && (game.key() != KEY_ROME_2
-                    && game.key() != KEY_ATTILA
-                    && game.key() != KEY_THRONES_OF_BRITANNIA)
-            {
+            if !game_uses_movie_masking(game) {
                 true
             } else if let Some(path) = self.paths().first() {
                 !path.starts_with(&data_path)
@@ -264,6 +297,21 @@ impl Mod {
         }
     }
 
+    /// Returns the creator name to show for the mod. Mods from a store always use the resolved name.
+    pub fn display_creator_name(&self) -> &str {
+        match (&self.store_id, &self.creator_name_override) {
+            (StoreId::None, Some(creator_name)) => creator_name,
+            _ => &self.creator_name,
+        }
+    }
+
+    /// Returns if the mod is a Shogun 2 map, which is uploaded as a bin with a folder name instead of a pack name.
+    pub fn is_map_mod(&self) -> bool {
+        self.id().ends_with(".bin")
+            && !self.file_name().is_empty()
+            && !self.file_name().ends_with(".pack")
+    }
+
     /// Function to get the alternative name for Shogun 2 map binaries.
     pub fn alt_name(&self) -> Option<String> {
         if !self.file_name().is_empty() && !self.file_name().ends_with(".pack") {
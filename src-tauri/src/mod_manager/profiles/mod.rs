@@ -10,7 +10,7 @@
 
 //! Module containing the centralized code for mod and load order management.
 
-use anyhow::Result;
+use anyhow::{Result, anyhow};
 use getset::*;
 use serde::{Deserialize, Serialize};
 use serde_json::to_string_pretty;
@@ -32,6 +32,9 @@ use super::load_order::LoadOrder;
 const FILE_NAME_START: &str = "profile_";
 const FILE_NAME_END: &str = ".json";
 
+/// Characters not allowed in profile names, as they're used as part of the file name.
+const INVALID_NAME_CHARS: [char; 9] = ['/', '\\', ':', '*', '?', '"', '<', '>', '|'];
+
 //-------------------------------------------------------------------------------//
 //                              Enums & Structs
 //-------------------------------------------------------------------------------//
@@ -54,6 +57,26 @@ pub struct Profile {
 //-------------------------------------------------------------------------------//
 
 impl Profile {
+    pub fn new(game: &GameInfo, name: &str, load_order: &LoadOrder) -> Result<Self> {
+        let name = name.trim();
+        if name.is_empty() {
+            return Err(anyhow!("Profile names cannot be empty."));
+        }
+
+        if name.contains(INVALID_NAME_CHARS) {
+            return Err(anyhow!(
+                "Profile name {} contains invalid characters.",
+                name
+            ));
+        }
+
+        Ok(Self {
+            id: name.to_owned(),
+            game: game.key().to_owned(),
+            load_order: load_order.clone(),
+        })
+    }
+
     pub fn profiles_for_game(
         app_handle: &tauri::AppHandle,
         game: &GameInfo,
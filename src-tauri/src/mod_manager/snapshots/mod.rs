@@ -0,0 +1,165 @@
+//---------------------------------------------------------------------------//
+// Copyright (c) 2017-2024 Ismael Gutiérrez González. All rights reserved.
+//
+// This file is part of the Rusted Launcher (Runcher) project,
+// which can be found here: https://github.com/Frodo45127/runcher.
+//
+// This file is licensed under the MIT license, which can be found here:
+// https://github.com/Frodo45127/runcher/blob/master/LICENSE.
+//---------------------------------------------------------------------------//
+
+//! Module containing the code for full snapshots of a game's config and load order.
+
+use anyhow::{Result, anyhow};
+use getset::*;
+use serde::{Deserialize, Serialize};
+use serde_json::to_string_pretty;
+
+use std::fs::{DirBuilder, File};
+use std::io::{BufReader, BufWriter, Read, Write};
+use std::path::Path;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use rpfm_lib::games::GameInfo;
+use rpfm_lib::integrations::log::error;
+use rpfm_lib::utils::*;
+
+use crate::mod_manager::game_config::GameConfig;
+use crate::settings::*;
+
+use super::load_order::LoadOrder;
+
+const FILE_NAME_START: &str = "snapshot_";
+const FILE_NAME_END: &str = ".json";
+
+/// Characters not allowed in labels, as they're used as part of the file name.
+const INVALID_LABEL_CHARS: [char; 9] = ['/', '\\', ':', '*', '?', '"', '<', '>', '|'];
+
+//-------------------------------------------------------------------------------//
+//                              Enums & Structs
+//-------------------------------------------------------------------------------//
+
+#[derive(Clone, Debug, Default, Getters, MutGetters, Setters, Serialize, Deserialize)]
+#[getset(get = "pub", get_mut = "pub", set = "pub")]
+pub struct Snapshot {
+    // Label of the snapshot. Multiple snapshots can share it.
+    label: String,
+
+    // Game this snapshot belongs to.
+    game: String,
+
+    // Time the snapshot was taken, in seconds since the unix epoch.
+    timestamp: u64,
+
+    // Game config at the time of the snapshot.
+    game_config: GameConfig,
+
+    // Load order at the time of the snapshot.
+    load_order: LoadOrder,
+}
+
+//-------------------------------------------------------------------------------//
+//                             Implementations
+//-------------------------------------------------------------------------------//
+
+impl Snapshot {
+    pub fn new(
+        game: &GameInfo,
+        label: &str,
+        game_config: &GameConfig,
+        load_order: &LoadOrder,
+    ) -> Result<Self> {
+        let label = label.trim();
+        if label.is_empty() {
+            return Err(anyhow!("Snapshot labels cannot be empty."));
+        }
+
+        if label.contains(INVALID_LABEL_CHARS) {
+            return Err(anyhow!(
+                "Snapshot label {} contains invalid characters.",
+                label
+            ));
+        }
+
+        Ok(Self {
+            label: label.to_owned(),
+            game: game.key().to_owned(),
+            timestamp: SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs(),
+            game_config: game_config.clone(),
+            load_order: load_order.clone(),
+        })
+    }
+
+    /// Returns all the snapshots of a game, sorted from oldest to newest.
+    pub fn snapshots_for_game(app_handle: &tauri::AppHandle, game: &GameInfo) -> Result<Vec<Self>> {
+        let path = snapshots_path(app_handle)?;
+        if !path.is_dir() {
+            return Ok(vec![]);
+        }
+
+        let file_name_start = format!("{FILE_NAME_START}{}_", game.key());
+        let mut snapshots = vec![];
+
+        let files = files_from_subdir(&path, false)?;
+        for file in files {
+            let file_name = file.file_name().unwrap().to_string_lossy();
+            if file_name.starts_with(&file_name_start) && file_name.ends_with(FILE_NAME_END) {
+                // Games with keys starting with this game's key also match the prefix, so check the game too.
+                // A broken snapshot shouldn't hide the rest, so skip it.
+                match Self::load(&file) {
+                    Ok(snapshot) => {
+                        if snapshot.game == game.key() {
+                            snapshots.push(snapshot);
+                        }
+                    }
+                    Err(load_error) => error!(
+                        "Error loading snapshot {}: {}",
+                        file.to_string_lossy(),
+                        load_error
+                    ),
+                }
+            }
+        }
+
+        snapshots.sort_by(|a, b| a.timestamp.cmp(&b.timestamp));
+        Ok(snapshots)
+    }
+
+    /// Returns the newest snapshot of a game with the provided label.
+    pub fn latest_with_label(
+        app_handle: &tauri::AppHandle,
+        game: &GameInfo,
+        label: &str,
+    ) -> Result<Self> {
+        Self::snapshots_for_game(app_handle, game)?
+            .into_iter()
+            .rev()
+            .find(|snapshot| snapshot.label == label.trim())
+            .ok_or_else(|| anyhow!("Snapshot {} not found.", label))
+    }
+
+    fn load(path: &Path) -> Result<Self> {
+        let mut file = BufReader::new(File::open(path)?);
+        let mut data = Vec::with_capacity(file.get_ref().metadata()?.len() as usize);
+        file.read_to_end(&mut data)?;
+
+        let snapshot: Self = serde_json::from_slice(&data)?;
+        Ok(snapshot)
+    }
+
+    pub fn save(&mut self, app_handle: &tauri::AppHandle) -> Result<()> {
+        let path = snapshots_path(app_handle)?.join(format!(
+            "{FILE_NAME_START}{}_{}_{}{FILE_NAME_END}",
+            self.game, self.timestamp, self.label
+        ));
+
+        // Make sure the path exists, as there's no snapshots folder until the first snapshot is taken.
+        if let Some(parent_folder) = path.parent() {
+            DirBuilder::new().recursive(true).create(parent_folder)?;
+        }
+
+        let mut file = BufWriter::new(File::create(path)?);
+        file.write_all(to_string_pretty(&self)?.as_bytes())?;
+        Ok(())
+    }
+}
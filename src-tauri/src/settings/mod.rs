@@ -13,7 +13,7 @@ use serde::{Deserialize, Serialize};
 use tauri::Manager;
 
 use std::cell::OnceCell;
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::fs::{DirBuilder, File};
 use std::path::{Path, PathBuf};
 use std::time::UNIX_EPOCH;
@@ -29,8 +29,10 @@ const SQL_SCRIPTS_EXTRACTED_FOLDER: &str = "sql_scripts_extracted";
 const SQL_SCRIPTS_LOCAL_FOLDER: &str = "sql_scripts_local";
 const SQL_SCRIPTS_REMOTE_FOLDER: &str = "sql_scripts_remote";
 const TEMP_PACKS_FOLDER: &str = "temp_packs";
+const TEMP_PACKS_DEBUG_FOLDER: &str = "temp_packs_debug";
 const SCHEMAS_FOLDER: &str = "schemas";
 const PROFILES_FOLDER: &str = "profiles";
+const SNAPSHOTS_FOLDER: &str = "snapshots";
 const GAME_CONFIG_FOLDER: &str = "game_config";
 const ERROR_FOLDER: &str = "error";
 const SETTINGS_FILE: &str = "settings.json";
@@ -42,6 +44,7 @@ const GAME_CONFIG_FILE: &str = "game_config.json";
 //-------------------------------------------------------------------------------//
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(default)]
 pub struct AppSettings {
     pub tree_open_state: HashMap<String, bool>,
     pub tree_filter_value: String,
@@ -61,6 +64,23 @@ pub struct AppSettings {
     pub check_sql_scripts_updates_on_start: bool,
     pub open_remote_mod_in_app: bool,
     pub launch_options: HashMap<String, String>,
+
+    /// Extra arguments passed as-is to twpatcher, after the ones generated from the launch options.
+    ///
+    /// Arguments are split on whitespace, unless quoted. Malformed arguments can make the patching fail.
+    pub twpatcher_extra_args: String,
+
+    /// Keep the temp packs generated on launch, copying them to a debug folder before they're replaced.
+    pub keep_temp_packs: bool,
+
+    /// Save the game config and load order to disk on every change. If disabled, they're only saved on demand, on launch and on close.
+    pub autosave: bool,
+
+    /// Named launch option presets, by game key and preset name. Managed by the backend only.
+    pub launch_presets: HashMap<String, BTreeMap<String, HashMap<String, String>>>,
+
+    /// Keys of the SQL scripts shipped by mods the user doesn't want to run, by game key. Managed by the backend only.
+    pub disabled_mod_scripts: HashMap<String, Vec<String>>,
 }
 
 //-------------------------------------------------------------------------------//
@@ -88,6 +108,11 @@ impl Default for AppSettings {
             check_sql_scripts_updates_on_start: true,
             open_remote_mod_in_app: false,
             launch_options: HashMap::new(),
+            twpatcher_extra_args: String::new(),
+            keep_temp_packs: false,
+            autosave: true,
+            launch_presets: HashMap::new(),
+            disabled_mod_scripts: HashMap::new(),
         }
     }
 }
@@ -157,6 +182,14 @@ impl AppSettings {
         Ok(PathBuf::from(path))
     }
 
+    /// Returns the secondary mods path override of the provided game if it has one, or the global one if not.
+    pub fn secondary_mods_path_for_game(&self, game: &str) -> Result<PathBuf> {
+        match self.paths.get(&format!("secondary_mods_path_{game}")) {
+            Some(path) if !path.is_empty() => Ok(PathBuf::from(path)),
+            _ => self.secondary_mods_path(),
+        }
+    }
+
     pub fn string(&self, key: &str) -> Result<String> {
         self.strings
             .get(key)
@@ -170,6 +203,32 @@ impl AppSettings {
         }
     }
 
+    /// This function returns all the launch option settings of the provided game, as they're stored in the settings.
+    pub fn launch_options_for_game(&self, game: &GameInfo) -> HashMap<String, String> {
+        let prefix = format!("{}:", game.key());
+        self.launch_options
+            .iter()
+            .filter(|(key, _)| key.starts_with(&prefix))
+            .map(|(key, value)| (key.to_owned(), value.to_owned()))
+            .collect()
+    }
+
+    /// This function replaces all the launch option settings of the provided game with the provided ones.
+    pub fn set_launch_options_for_game(
+        &mut self,
+        game: &GameInfo,
+        options: &HashMap<String, String>,
+    ) {
+        let prefix = format!("{}:", game.key());
+        self.launch_options.retain(|key, _| !key.starts_with(&prefix));
+        self.launch_options.extend(
+            options
+                .iter()
+                .filter(|(key, _)| key.starts_with(&prefix))
+                .map(|(key, value)| (key.to_owned(), value.to_owned())),
+        );
+    }
+
     pub fn load(app_handle: &tauri::AppHandle) -> Result<Self> {
         let config_path = get_config_path(&app_handle)?;
         if !config_path.exists() {
@@ -268,6 +327,12 @@ pub fn temp_packs_folder(app_handle: &tauri::AppHandle, game: &GameInfo) -> Resu
         .join(game.key()))
 }
 
+pub fn temp_packs_debug_folder(app_handle: &tauri::AppHandle, game: &GameInfo) -> Result<PathBuf> {
+    Ok(config_path(app_handle)?
+        .join(TEMP_PACKS_DEBUG_FOLDER)
+        .join(game.key()))
+}
+
 pub fn sql_scripts_extracted_path(app_handle: &tauri::AppHandle) -> Result<PathBuf> {
     Ok(config_path(app_handle)?.join(SQL_SCRIPTS_EXTRACTED_FOLDER))
 }
@@ -300,6 +365,10 @@ pub fn profiles_path(app_handle: &tauri::AppHandle) -> Result<PathBuf> {
     Ok(config_path(app_handle)?.join(PROFILES_FOLDER))
 }
 
+pub fn snapshots_path(app_handle: &tauri::AppHandle) -> Result<PathBuf> {
+    Ok(config_path(app_handle)?.join(SNAPSHOTS_FOLDER))
+}
+
 pub fn last_game_update_date(game: &GameInfo, game_path: &Path) -> Result<u64> {
     Ok(if let Some(exe_path) = game.executable_path(game_path) {
         if let Ok(exe) = File::open(exe_path) {
@@ -8,13 +8,17 @@
 // https://github.com/Frodo45127/runcher/blob/master/LICENSE.
 //---------------------------------------------------------------------------//
 
+use rpfm_lib::integrations::log::error;
 use serde::Serialize;
 /// Updater code. Mostly copied from the tauri docs, that's why it has custom error and result types.
 use std::sync::Mutex;
-use tauri::{AppHandle, State, ipc::Channel};
-use tauri_plugin_updater::{Update, UpdaterExt};
+use tauri::{AppHandle, Emitter, State, ipc::Channel};
+use tauri_plugin_updater::{Update, Updater, UpdaterExt};
 use url::Url;
 
+const UPDATE_ENDPOINT: &str =
+    "https://github.com/Frodo45127/runcher_tauri/releases/latest/download/latest.json";
+
 //-------------------------------------------------------------------------------//
 //                             Structs & Enums
 //-------------------------------------------------------------------------------//
@@ -47,6 +51,17 @@ pub struct UpdateMetadata {
     current_version: String,
 }
 
+/// Result of a check for updates that doesn't queue anything for install.
+#[derive(Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateCheck {
+    available: bool,
+    version: Option<String>,
+    current_version: String,
+    notes: Option<String>,
+    date: Option<String>,
+}
+
 #[derive(Clone, Serialize)]
 #[serde(tag = "event", content = "data")]
 pub enum DownloadEvent {
@@ -59,6 +74,9 @@ pub enum DownloadEvent {
         chunk_length: usize,
     },
     Finished,
+    Failed {
+        error: String,
+    },
 }
 
 //-------------------------------------------------------------------------------//
@@ -70,17 +88,7 @@ pub async fn fetch_update(
     app: AppHandle,
     pending_update: State<'_, PendingUpdate>,
 ) -> Result<Option<UpdateMetadata>> {
-    let url = Url::parse(
-        "https://github.com/Frodo45127/runcher_tauri/releases/latest/download/latest.json",
-    )
-    .expect("invalid URL");
-
-    let update = app
-        .updater_builder()
-        .endpoints(vec![url])?
-        .build()?
-        .check()
-        .await?;
+    let update = updater(&app)?.check().await?;
 
     let update_metadata = update.as_ref().map(|update| UpdateMetadata {
         version: update.version.clone(),
@@ -92,8 +100,47 @@ pub async fn fetch_update(
     Ok(update_metadata)
 }
 
+/// Checks if there's a newer version available, without touching the pending update.
+#[tauri::command]
+pub async fn check_for_update(app: AppHandle) -> Result<UpdateCheck> {
+    let update = updater(&app)?.check().await?;
+
+    Ok(match update {
+        Some(update) => UpdateCheck {
+            available: true,
+            version: Some(update.version.clone()),
+            current_version: update.current_version.clone(),
+            notes: update.body.clone(),
+            date: update.date.map(|date| date.to_string()),
+        },
+        None => UpdateCheck {
+            available: false,
+            version: None,
+            current_version: app.package_info().version.to_string(),
+            notes: None,
+            date: None,
+        },
+    })
+}
+
+/// Checks for updates in the background, and notifies the UI if there's one available.
+pub fn check_for_update_on_start(app: &AppHandle) {
+    let app = app.clone();
+    tauri::async_runtime::spawn(async move {
+        match check_for_update(app.clone()).await {
+            Ok(update_check) => {
+                if update_check.available {
+                    let _ = app.emit("update://available", update_check);
+                }
+            }
+            Err(error) => error!("Error checking for updates: {}", error),
+        }
+    });
+}
+
 #[tauri::command]
 pub async fn install_update(
+    app: AppHandle,
     pending_update: State<'_, PendingUpdate>,
     on_event: Channel<DownloadEvent>,
 ) -> Result<()> {
@@ -103,9 +150,8 @@ pub async fn install_update(
 
     let mut started = false;
 
-    // TODO: Replace this with download_and_install when the app is ready.
-    update
-        .download(
+    let result = update
+        .download_and_install(
             |chunk_length, content_length| {
                 if !started {
                     let _ = on_event.send(DownloadEvent::Started { content_length });
@@ -118,7 +164,21 @@ pub async fn install_update(
                 let _ = on_event.send(DownloadEvent::Finished);
             },
         )
-        .await?;
+        .await;
+
+    // If it fails mid-way, tell the UI so it doesn't wait forever on a half-filled progress bar.
+    if let Err(error) = result {
+        let _ = on_event.send(DownloadEvent::Failed {
+            error: error.to_string(),
+        });
+        return Err(error.into());
+    }
+
+    // The update is only applied on the next start, so relaunch the app.
+    app.restart();
+}
 
-    Ok(())
+fn updater(app: &AppHandle) -> Result<Updater> {
+    let url = Url::parse(UPDATE_ENDPOINT).expect("invalid URL");
+    Ok(app.updater_builder().endpoints(vec![url])?.build()?)
 }
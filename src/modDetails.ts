@@ -1,6 +1,5 @@
 import { invoke } from "@tauri-apps/api/core";
 import { Main } from "./main";
-import { steamFormatToHtml } from "./utils/steamFormat";
 
 export class ModDetailsPanel {
   private currentItemId: string;
@@ -86,7 +85,7 @@ export class ModDetailsPanel {
 
     if (modDetails.description) {
       this.detailDescriptionSection.classList.remove('hidden');
-      this.detailDescription.innerHTML = steamFormatToHtml(modDetails.description);
+      this.detailDescription.innerHTML = modDetails.description;
     } else {
       this.detailDescriptionSection.classList.add('hidden');
       this.detailDescription.innerHTML = '';
@@ -8,6 +8,10 @@ export interface TreeItem {
   id: string;
   name: string;
   flags: string;
+  is_outdated: boolean;
+  data_older_than_secondary: boolean;
+  data_older_than_content: boolean;
+  secondary_older_than_content: boolean;
   location: string;
   creator: string;
   type: string;
@@ -226,6 +230,7 @@ export class ModTree {
           <div class="item-details">
             <div class="item-row">
               <div class="item-name"><i>${item.name}</i></div>
+              ${item.flags ? `<i class="fa-solid fa-triangle-exclamation item-flags" title="${item.flags}"></i>` : ''}
             </div>
             <div class="item-row item-info">
               <div class="item-type">${item.type || ''}</div>
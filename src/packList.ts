@@ -12,6 +12,7 @@ export interface ListItem {
   type: string;
   order: number;
   location: string;
+  enabled: boolean;
 }
 
 const enum LoadOrderDirectionMove {
@@ -23,6 +23,9 @@ export interface AppSettings {
   check_sql_scripts_updates_on_start: boolean;
   open_remote_mod_in_app: boolean;
   launch_options: { [key: string]: string };
+  twpatcher_extra_args: string;
+  keep_temp_packs: boolean;
+  autosave: boolean;
 }
 
 export class SettingsManager {
@@ -49,7 +52,10 @@ export class SettingsManager {
       check_schema_updates_on_start: true,
       check_sql_scripts_updates_on_start: true,
       open_remote_mod_in_app: false,
-      launch_options: {}
+      launch_options: {},
+      twpatcher_extra_args: '',
+      keep_temp_packs: false,
+      autosave: true
     };
   }
 
@@ -125,7 +131,10 @@ export class SettingsManager {
           check_schema_updates_on_start: this.appSettings.check_schema_updates_on_start,
           check_sql_scripts_updates_on_start: this.appSettings.check_sql_scripts_updates_on_start,
           open_remote_mod_in_app: this.appSettings.open_remote_mod_in_app,
-          launch_options: this.appSettings.launch_options
+          launch_options: this.appSettings.launch_options,
+          twpatcher_extra_args: this.appSettings.twpatcher_extra_args,
+          keep_temp_packs: this.appSettings.keep_temp_packs,
+          autosave: this.appSettings.autosave
         }
       });
     } catch (error) {